@@ -0,0 +1,473 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime-dispatched keys and signatures of any of the supported algorithms.
+
+use std::str::FromStr;
+
+use amplify::confinement::Confined;
+use baid58::Baid58ParseError;
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+#[cfg(feature = "bls")]
+use super::bls12381::BLS_PAYLOAD_LEN;
+#[cfg(feature = "mldsa")]
+use super::hybrid::HYBRID_PAYLOAD_LEN;
+#[cfg(feature = "mldsa")]
+use super::mldsa::MLDSA_PAYLOAD_LEN;
+use super::{
+    invalid_pk, parse_pk_payload, AgreementError, BatchItem, BatchVerifyError, EcdsaPk, EcdsaSig,
+    EcdsaSk, KeyAgreement, Pk, RistrettoPk, RistrettoSig, RistrettoSk, Secp256k1Pk, Secp256k1Sig,
+    Secp256k1Sk, SharedSecret, Sig, Sk,
+};
+#[cfg(feature = "bls")]
+use super::{BlsPk, BlsSig, BlsSk};
+#[cfg(feature = "mldsa")]
+use super::{HybridPk, HybridSig, HybridSk, MlDsaPk, MlDsaSig, MlDsaSk};
+#[cfg(feature = "p256")]
+use super::{P256Pk, P256Sig, P256Sk};
+#[cfg(feature = "slhdsa")]
+use super::{SlhDsaPk, SlhDsaSig, SlhDsaSk};
+use crate::{BindleContent, IdCert, Identity, LongFingerprint, Revocation, SigHash, LIB_NAME_SSID};
+
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode, From)]
+#[strict_type(lib = LIB_NAME_SSID, tags = custom, dumb = Self::Ristretto(strict_dumb!()))]
+pub enum AnySk {
+    #[from]
+    #[strict_type(tag = 1)]
+    Ristretto(RistrettoSk),
+
+    #[from]
+    #[strict_type(tag = 2)]
+    Bip340(Secp256k1Sk),
+
+    #[from]
+    #[strict_type(tag = 3)]
+    Ecdsa(EcdsaSk),
+
+    #[cfg(feature = "p256")]
+    #[from]
+    #[strict_type(tag = 4)]
+    P256(P256Sk),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 5)]
+    MlDsa(MlDsaSk),
+
+    #[cfg(feature = "slhdsa")]
+    #[from]
+    #[strict_type(tag = 6)]
+    SlhDsa(SlhDsaSk),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 7)]
+    Hybrid(HybridSk),
+
+    #[cfg(feature = "bls")]
+    #[from]
+    #[strict_type(tag = 8)]
+    Bls(BlsSk),
+}
+
+impl StrictSerialize for AnySk {}
+impl StrictDeserialize for AnySk {}
+
+impl Sk for AnySk {
+    type Sig = AnySig;
+
+    /// Generates [`RistrettoSk`], which is the default key type.
+    fn generate() -> Self { Self::Ristretto(RistrettoSk::generate()) }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let message: SigHash = message.into();
+        match self {
+            AnySk::Ristretto(sk) => sk.sign(message).into(),
+            AnySk::Bip340(sk) => sk.sign(message).into(),
+            AnySk::Ecdsa(sk) => sk.sign(message).into(),
+            #[cfg(feature = "p256")]
+            AnySk::P256(sk) => sk.sign(message).into(),
+            #[cfg(feature = "mldsa")]
+            AnySk::MlDsa(sk) => sk.sign(message).into(),
+            #[cfg(feature = "slhdsa")]
+            AnySk::SlhDsa(sk) => sk.sign(message).into(),
+            #[cfg(feature = "mldsa")]
+            AnySk::Hybrid(sk) => sk.sign(message).into(),
+            #[cfg(feature = "bls")]
+            AnySk::Bls(sk) => sk.sign(message).into(),
+        }
+    }
+}
+
+impl BindleContent for AnySk {
+    const MAGIC: [u8; 4] = *b"SSKT";
+    const PLATE_TITLE: &'static str = "SSID TAGGED SECRET KEY";
+    type Id = AnyPk;
+
+    fn bindle_id(&self) -> Self::Id { AnyPk::with(self) }
+}
+
+/// Public key of any of the supported algorithms, which type is detected at
+/// runtime from its [`Pk::ID`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display, From)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID, tags = custom, dumb = Self::Ristretto(strict_dumb!()))]
+#[display(inner)]
+pub enum AnyPk {
+    #[from]
+    #[strict_type(tag = 1)]
+    Ristretto(RistrettoPk),
+
+    #[from]
+    #[strict_type(tag = 2)]
+    Bip340(Secp256k1Pk),
+
+    #[from]
+    #[strict_type(tag = 3)]
+    Ecdsa(EcdsaPk),
+
+    #[cfg(feature = "p256")]
+    #[from]
+    #[strict_type(tag = 4)]
+    P256(P256Pk),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 5)]
+    MlDsa(MlDsaPk),
+
+    #[cfg(feature = "slhdsa")]
+    #[from]
+    #[strict_type(tag = 6)]
+    SlhDsa(SlhDsaPk),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 7)]
+    Hybrid(HybridPk),
+
+    #[cfg(feature = "bls")]
+    #[from]
+    #[strict_type(tag = 8)]
+    Bls(BlsPk),
+}
+
+impl AnyPk {
+    /// Returns [`Pk::ID`] of the underlying key type.
+    pub fn algo_id(&self) -> u8 {
+        match self {
+            AnyPk::Ristretto(_) => RistrettoPk::ID,
+            AnyPk::Bip340(_) => Secp256k1Pk::ID,
+            AnyPk::Ecdsa(_) => EcdsaPk::ID,
+            #[cfg(feature = "p256")]
+            AnyPk::P256(_) => P256Pk::ID,
+            #[cfg(feature = "mldsa")]
+            AnyPk::MlDsa(_) => MlDsaPk::ID,
+            #[cfg(feature = "slhdsa")]
+            AnyPk::SlhDsa(_) => SlhDsaPk::ID,
+            #[cfg(feature = "mldsa")]
+            AnyPk::Hybrid(_) => HybridPk::ID,
+            #[cfg(feature = "bls")]
+            AnyPk::Bls(_) => BlsPk::ID,
+        }
+    }
+}
+
+impl Pk for AnyPk {
+    type Sk = AnySk;
+    /// Runtime-dispatched keys don't have a single algorithm id; use
+    /// [`AnyPk::algo_id`] instead.
+    const ID: u8 = 0;
+
+    fn with(sk: &Self::Sk) -> Self {
+        match sk {
+            AnySk::Ristretto(sk) => RistrettoPk::with(sk).into(),
+            AnySk::Bip340(sk) => Secp256k1Pk::with(sk).into(),
+            AnySk::Ecdsa(sk) => EcdsaPk::with(sk).into(),
+            #[cfg(feature = "p256")]
+            AnySk::P256(sk) => P256Pk::with(sk).into(),
+            #[cfg(feature = "mldsa")]
+            AnySk::MlDsa(sk) => MlDsaPk::with(sk).into(),
+            #[cfg(feature = "slhdsa")]
+            AnySk::SlhDsa(sk) => SlhDsaPk::with(sk).into(),
+            #[cfg(feature = "mldsa")]
+            AnySk::Hybrid(sk) => HybridPk::with(sk).into(),
+            #[cfg(feature = "bls")]
+            AnySk::Bls(sk) => BlsPk::with(sk).into(),
+        }
+    }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let message: SigHash = message.into();
+        match (self, sig) {
+            (AnyPk::Ristretto(pk), AnySig::Ristretto(sig)) => pk.verify(message, sig),
+            (AnyPk::Bip340(pk), AnySig::Bip340(sig)) => pk.verify(message, sig),
+            (AnyPk::Ecdsa(pk), AnySig::Ecdsa(sig)) => pk.verify(message, sig),
+            #[cfg(feature = "p256")]
+            (AnyPk::P256(pk), AnySig::P256(sig)) => pk.verify(message, sig),
+            #[cfg(feature = "mldsa")]
+            (AnyPk::MlDsa(pk), AnySig::MlDsa(sig)) => pk.verify(message, sig),
+            #[cfg(feature = "slhdsa")]
+            (AnyPk::SlhDsa(pk), AnySig::SlhDsa(sig)) => pk.verify(message, sig),
+            #[cfg(feature = "mldsa")]
+            (AnyPk::Hybrid(pk), AnySig::Hybrid(sig)) => pk.verify(message, sig),
+            #[cfg(feature = "bls")]
+            (AnyPk::Bls(pk), AnySig::Bls(sig)) => pk.verify(message, sig),
+            _ => false,
+        }
+    }
+
+    /// Groups the entries by algorithm, batch-verifying the ones which support
+    /// it and verifying the rest individually.
+    fn verify_batch(batch: &[BatchItem<Self>]) -> Result<(), BatchVerifyError> {
+        let mut invalid = vec![];
+        #[cfg(feature = "bls")]
+        let (mut bls_nos, mut bls_batch) = (vec![], vec![]);
+        for (no, (pk, message, sig)) in batch.iter().enumerate() {
+            match (pk, sig) {
+                #[cfg(feature = "bls")]
+                (AnyPk::Bls(pk), AnySig::Bls(sig)) => {
+                    bls_nos.push(no);
+                    bls_batch.push((*pk, *message, *sig));
+                }
+                _ if !pk.verify(*message, sig) => invalid.push(no),
+                _ => {}
+            }
+        }
+        #[cfg(feature = "bls")]
+        if let Err(err) = BlsPk::verify_batch(&bls_batch) {
+            invalid.extend(err.invalid.into_iter().map(|no| bls_nos[no]));
+            invalid.sort_unstable();
+        }
+        BatchVerifyError::check(invalid)
+    }
+
+    fn to_mnemonic(&self) -> String {
+        match self {
+            AnyPk::Ristretto(pk) => pk.to_mnemonic(),
+            AnyPk::Bip340(pk) => pk.to_mnemonic(),
+            AnyPk::Ecdsa(pk) => pk.to_mnemonic(),
+            #[cfg(feature = "p256")]
+            AnyPk::P256(pk) => pk.to_mnemonic(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::MlDsa(pk) => pk.to_mnemonic(),
+            #[cfg(feature = "slhdsa")]
+            AnyPk::SlhDsa(pk) => pk.to_mnemonic(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::Hybrid(pk) => pk.to_mnemonic(),
+            #[cfg(feature = "bls")]
+            AnyPk::Bls(pk) => pk.to_mnemonic(),
+        }
+    }
+
+    /// Returns data of the underlying key, not including the enum tag.
+    fn to_key_data(&self) -> Vec<u8> {
+        match self {
+            AnyPk::Ristretto(pk) => pk.to_key_data(),
+            AnyPk::Bip340(pk) => pk.to_key_data(),
+            AnyPk::Ecdsa(pk) => pk.to_key_data(),
+            #[cfg(feature = "p256")]
+            AnyPk::P256(pk) => pk.to_key_data(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::MlDsa(pk) => pk.to_key_data(),
+            #[cfg(feature = "slhdsa")]
+            AnyPk::SlhDsa(pk) => pk.to_key_data(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::Hybrid(pk) => pk.to_key_data(),
+            #[cfg(feature = "bls")]
+            AnyPk::Bls(pk) => pk.to_key_data(),
+        }
+    }
+
+    /// Matches the fingerprint of the underlying key.
+    fn long_fingerprint(&self) -> LongFingerprint {
+        match self {
+            AnyPk::Ristretto(pk) => pk.long_fingerprint(),
+            AnyPk::Bip340(pk) => pk.long_fingerprint(),
+            AnyPk::Ecdsa(pk) => pk.long_fingerprint(),
+            #[cfg(feature = "p256")]
+            AnyPk::P256(pk) => pk.long_fingerprint(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::MlDsa(pk) => pk.long_fingerprint(),
+            #[cfg(feature = "slhdsa")]
+            AnyPk::SlhDsa(pk) => pk.long_fingerprint(),
+            #[cfg(feature = "mldsa")]
+            AnyPk::Hybrid(pk) => pk.long_fingerprint(),
+            #[cfg(feature = "bls")]
+            AnyPk::Bls(pk) => pk.long_fingerprint(),
+        }
+    }
+}
+
+impl KeyAgreement for AnyPk {
+    fn agree(&self, sk: &Self::Sk) -> Result<SharedSecret, AgreementError> {
+        match (self, sk) {
+            (AnyPk::Ristretto(pk), AnySk::Ristretto(sk)) => pk.agree(sk),
+            (AnyPk::Bip340(pk), AnySk::Bip340(sk)) => pk.agree(sk),
+            (AnyPk::Ecdsa(pk), AnySk::Ecdsa(sk)) => pk.agree(sk),
+            (AnyPk::Ristretto(_) | AnyPk::Bip340(_) | AnyPk::Ecdsa(_), _) => {
+                Err(AgreementError::AlgoMismatch)
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(AgreementError::Unsupported),
+        }
+    }
+}
+
+fn unknown_algo(id: u8) -> Baid58ParseError { invalid_pk(format!("unknown key algorithm id {id}")) }
+
+impl FromStr for AnyPk {
+    type Err = Baid58ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The string may match at most a single payload length, so if none of them
+        // matches we return the length mismatch error from the last attempt.
+        let mut err = None;
+        match parse_pk_payload::<33>(s) {
+            Ok(payload) if payload[0] == RistrettoPk::ID => {
+                return RistrettoPk::try_from(payload).map(Self::Ristretto)
+            }
+            Ok(payload) if payload[0] == Secp256k1Pk::ID => {
+                return Secp256k1Pk::try_from(payload).map(Self::Bip340)
+            }
+            #[cfg(feature = "slhdsa")]
+            Ok(payload) if payload[0] == SlhDsaPk::ID => {
+                return SlhDsaPk::try_from(payload).map(Self::SlhDsa)
+            }
+            Ok(payload) => return Err(unknown_algo(payload[0])),
+            Err(e) => err = Some(e),
+        }
+        match parse_pk_payload::<34>(s) {
+            Ok(payload) if payload[0] == EcdsaPk::ID => {
+                return EcdsaPk::try_from(payload).map(Self::Ecdsa)
+            }
+            #[cfg(feature = "p256")]
+            Ok(payload) if payload[0] == P256Pk::ID => {
+                return P256Pk::try_from(payload).map(Self::P256)
+            }
+            Ok(payload) => return Err(unknown_algo(payload[0])),
+            Err(e) => err = Some(e),
+        }
+        #[cfg(feature = "bls")]
+        match parse_pk_payload::<BLS_PAYLOAD_LEN>(s) {
+            Ok(payload) if payload[0] == BlsPk::ID => {
+                return BlsPk::try_from(payload).map(Self::Bls)
+            }
+            Ok(payload) => return Err(unknown_algo(payload[0])),
+            Err(e) => err = Some(e),
+        }
+        #[cfg(feature = "mldsa")]
+        match parse_pk_payload::<MLDSA_PAYLOAD_LEN>(s) {
+            Ok(payload) if payload[0] == MlDsaPk::ID => {
+                return MlDsaPk::try_from(payload).map(Self::MlDsa)
+            }
+            Ok(payload) => return Err(unknown_algo(payload[0])),
+            Err(e) => err = Some(e),
+        }
+        #[cfg(feature = "mldsa")]
+        match parse_pk_payload::<HYBRID_PAYLOAD_LEN>(s) {
+            Ok(payload) if payload[0] == HybridPk::ID => {
+                return HybridPk::try_from(payload).map(Self::Hybrid)
+            }
+            Ok(payload) => return Err(unknown_algo(payload[0])),
+            Err(e) => err = Some(e),
+        }
+        Err(err.expect("at least two different payload lengths are always tried"))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, From)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID, tags = custom, dumb = Self::Ristretto(strict_dumb!()))]
+pub enum AnySig {
+    #[from]
+    #[strict_type(tag = 1)]
+    Ristretto(RistrettoSig),
+
+    #[from]
+    #[strict_type(tag = 2)]
+    Bip340(Secp256k1Sig),
+
+    #[from]
+    #[strict_type(tag = 3)]
+    Ecdsa(EcdsaSig),
+
+    #[cfg(feature = "p256")]
+    #[from]
+    #[strict_type(tag = 4)]
+    P256(P256Sig),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 5)]
+    MlDsa(MlDsaSig),
+
+    #[cfg(feature = "slhdsa")]
+    #[from]
+    #[strict_type(tag = 6)]
+    SlhDsa(SlhDsaSig),
+
+    #[cfg(feature = "mldsa")]
+    #[from]
+    #[strict_type(tag = 7)]
+    Hybrid(HybridSig),
+
+    #[cfg(feature = "bls")]
+    #[from]
+    #[strict_type(tag = 8)]
+    Bls(BlsSig),
+}
+
+impl Sig for AnySig {}
+
+impl<K: Pk + Into<AnyPk>> Identity<K> {
+    pub fn into_any(self) -> Identity<AnyPk> {
+        Identity {
+            key: self.key.into(),
+            seal: self.seal,
+        }
+    }
+}
+
+impl<K: Pk + Into<AnyPk>> Revocation<K> {
+    pub fn into_any(self) -> Revocation<AnyPk> {
+        Revocation {
+            new_identity: self.new_identity.into_any(),
+            revocation_proof: self.revocation_proof,
+        }
+    }
+}
+
+impl<K: Pk + Into<AnyPk>> IdCert<K>
+where <K::Sk as Sk>::Sig: Into<AnySig>
+{
+    pub fn into_any(self) -> IdCert<AnyPk> {
+        IdCert {
+            revocations: Confined::try_from_iter(
+                self.revocations.into_iter().map(Revocation::into_any),
+            )
+            .expect("the same number of revocations"),
+            genesis_id: self.genesis_id.into_any(),
+            genesis_sig: self.genesis_sig.into(),
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use secp256k1::{ecdh, schnorr, Keypair, Message, Parity, SecretKey, XOnlyPublicKey, SECP256K1};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::Zeroizing;
+
+use super::{
+    check_pk_id, invalid_pk, parse_pk_payload, AgreementError, KeyAgreement, Pk, SharedSecret, Sig,
+};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+/// X coordinate of the secp256k1 generator point, used as a dumb public key.
+const GENERATOR_X: [u8; 32] = [
+    0x79, 0xBE, 0x66, 0x7E, 0xF9, 0xDC, 0xBB, 0xAC, 0x55, 0xA0, 0x62, 0x95, 0xCE, 0x87, 0x0B, 0x07,
+    0x02, 0x9B, 0xFC, 0xDB, 0x2D, 0xCE, 0x28, 0xD9, 0x59, 0xF2, 0x81, 0x5B, 0x16, 0xF8, 0x17, 0x98,
+];
+
+pub struct Secp256k1Sk(Keypair);
+
+impl StrictType for Secp256k1Sk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for Secp256k1Sk {}
+impl StrictTuple for Secp256k1Sk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for Secp256k1Sk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(Zeroizing::new(self.0.secret_bytes()).deref())
+    }
+}
+impl StrictDecode for Secp256k1Sk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = Zeroizing::new(r.read_field::<[u8; 32]>()?);
+            Keypair::from_seckey_slice(SECP256K1, &data[..])
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for Secp256k1Sk {
+    fn strict_dumb() -> Self {
+        Self(Keypair::from_seckey_slice(SECP256K1, &[0xFAu8; 32]).expect("valid secret key"))
+    }
+}
+impl Drop for Secp256k1Sk {
+    fn drop(&mut self) { self.0.non_secure_erase() }
+}
+impl StrictSerialize for Secp256k1Sk {}
+impl StrictDeserialize for Secp256k1Sk {}
+
+impl Sk for Secp256k1Sk {
+    type Sig = Secp256k1Sig;
+
+    fn generate() -> Self { Self(Keypair::new(SECP256K1, &mut rand::thread_rng())) }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let msg = Message::from_digest(message.into().to_byte_array());
+        Secp256k1Sig(SECP256K1.sign_schnorr(&msg, &self.0))
+    }
+}
+
+impl BindleContent for Secp256k1Sk {
+    const MAGIC: [u8; 4] = *b"SSKB";
+    const PLATE_TITLE: &'static str = "SSID BIP340 SECRET KEY";
+    type Id = Secp256k1Pk;
+
+    fn bindle_id(&self) -> Self::Id { Secp256k1Pk::with(self) }
+}
+
+/// BIP340 x-only public key over secp256k1 curve.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct Secp256k1Pk(XOnlyPublicKey);
+
+impl TryFrom<[u8; 33]> for Secp256k1Pk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; 33]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        XOnlyPublicKey::from_slice(&value[1..]).map(Self).map_err(invalid_pk)
+    }
+}
+
+impl ToBaid58<33> for Secp256k1Pk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; 33] {
+        let mut payload = [0u8; 33];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0.serialize());
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for Secp256k1Pk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl Secp256k1Pk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+impl Pk for Secp256k1Pk {
+    type Sk = Secp256k1Sk;
+    const ID: u8 = 2;
+
+    fn with(sk: &Self::Sk) -> Self { Self(sk.0.x_only_public_key().0) }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let msg = Message::from_digest(message.into().to_byte_array());
+        SECP256K1.verify_schnorr(&sig.0, &msg, &self.0).is_ok()
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+/// ECDH over secp256k1, using the x-coordinate of the shared point as the
+/// shared secret, which doesn't depend on the parity of the x-only keys.
+impl KeyAgreement for Secp256k1Pk {
+    fn agree(&self, sk: &Self::Sk) -> Result<SharedSecret, AgreementError> {
+        let pk = self.0.public_key(Parity::Even);
+        let mut sk = SecretKey::from_keypair(&sk.0);
+        let point = Zeroizing::new(ecdh::shared_secret_point(&pk, &sk));
+        sk.non_secure_erase();
+        let mut x = [0u8; 32];
+        x.copy_from_slice(&point[..32]);
+        SharedSecret::with(x)
+    }
+}
+
+impl StrictType for Secp256k1Pk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for Secp256k1Pk {}
+impl StrictTuple for Secp256k1Pk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for Secp256k1Pk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0.serialize())
+    }
+}
+impl StrictDecode for Secp256k1Pk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 32] = r.read_field()?;
+            XOnlyPublicKey::from_slice(&data)
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for Secp256k1Pk {
+    fn strict_dumb() -> Self {
+        Self(XOnlyPublicKey::from_slice(&GENERATOR_X).expect("generator point"))
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Secp256k1Sig(schnorr::Signature);
+
+impl Sig for Secp256k1Sig {}
+
+impl StrictType for Secp256k1Sig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for Secp256k1Sig {}
+impl StrictTuple for Secp256k1Sig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for Secp256k1Sig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0.serialize())
+    }
+}
+impl StrictDecode for Secp256k1Sig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 64] = r.read_field()?;
+            schnorr::Signature::from_slice(&data)
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for Secp256k1Sig {
+    fn strict_dumb() -> Self {
+        Self(schnorr::Signature::from_slice(&[0xFAu8; 64]).expect("fixed length"))
+    }
+}
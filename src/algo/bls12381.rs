@@ -0,0 +1,401 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS signatures over BLS12-381 curve with public keys in G1 and signatures
+//! in G2 (the "minimal public key size" variant).
+
+use std::collections::HashSet;
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use amplify::confinement::{self, Confined, SmallVec};
+use amplify::Bytes32;
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use blst::min_pk::{AggregateSignature, PublicKey, SecretKey, Signature};
+use blst::{blst_scalar, BLST_ERROR};
+use rand::{thread_rng, Rng};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::Zeroizing;
+
+use super::{
+    check_pk_id, invalid_pk, parse_pk_payload, verify_each, BatchItem, BatchVerifyError, Pk, Sig,
+};
+use crate::{
+    Bindle, BindleContent, IdCert, SigCert, SigHash, Signature as SsiSignature, Sk, LIB_NAME_SSID,
+};
+
+/// Domain separation tag of the proof-of-possession BLS ciphersuite.
+///
+/// Since all signed messages are tagged hashes, self-signatures of identities
+/// serve as the proofs of possession of their keys, protecting aggregated
+/// signatures from rogue key attacks.
+const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+const BLS_PK_LEN: usize = 48;
+const BLS_SIG_LEN: usize = 96;
+/// Length of the baid58 payload of [`BlsPk`], including the key id.
+pub(crate) const BLS_PAYLOAD_LEN: usize = BLS_PK_LEN + 1;
+/// Number of random bits used to weight each signature in batch verification.
+const BATCH_RAND_BITS: usize = 64;
+
+/// Compressed generator of G1.
+const GENERATOR: [u8; BLS_PK_LEN] = [
+    0x97, 0xF1, 0xD3, 0xA7, 0x31, 0x97, 0xD7, 0x94, 0x26, 0x95, 0x63, 0x8C, 0x4F, 0xA9, 0xAC, 0x0F,
+    0xC3, 0x68, 0x8C, 0x4F, 0x97, 0x74, 0xB9, 0x05, 0xA1, 0x4E, 0x3A, 0x3F, 0x17, 0x1B, 0xAC, 0x58,
+    0x6C, 0x55, 0xE8, 0x3F, 0xF9, 0x7A, 0x1A, 0xEF, 0xFB, 0x3A, 0xF0, 0x0A, 0xDB, 0x22, 0xC6, 0xBB,
+];
+
+pub struct BlsSk(SecretKey);
+
+impl StrictType for BlsSk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for BlsSk {}
+impl StrictTuple for BlsSk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for BlsSk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(Zeroizing::new(self.0.to_bytes()).deref())
+    }
+}
+impl StrictDecode for BlsSk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = Zeroizing::new(r.read_field::<[u8; 32]>()?);
+            SecretKey::from_bytes(&data[..])
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(format!("{err:?}")))
+        })
+    }
+}
+impl StrictDumb for BlsSk {
+    fn strict_dumb() -> Self {
+        Self(SecretKey::key_gen(&[0xFAu8; 32], &[]).expect("valid key material"))
+    }
+}
+impl StrictSerialize for BlsSk {}
+impl StrictDeserialize for BlsSk {}
+
+impl Sk for BlsSk {
+    type Sig = BlsSig;
+
+    fn generate() -> Self {
+        let mut ikm = Zeroizing::new([0u8; 32]);
+        thread_rng().fill(&mut ikm[..]);
+        Self(SecretKey::key_gen(&ikm[..], &[]).expect("valid key material"))
+    }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        BlsSig(self.0.sign(message.into().as_slice(), DST, &[]).compress())
+    }
+}
+
+impl BindleContent for BlsSk {
+    const MAGIC: [u8; 4] = *b"SSKL";
+    const PLATE_TITLE: &'static str = "SSID BLS SECRET KEY";
+    type Id = BlsPk;
+
+    fn bindle_id(&self) -> Self::Id { BlsPk::with(self) }
+}
+
+/// Compressed BLS12-381 G1 public key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct BlsPk([u8; BLS_PK_LEN]);
+
+impl TryFrom<[u8; BLS_PAYLOAD_LEN]> for BlsPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; BLS_PAYLOAD_LEN]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        let mut data = [0u8; BLS_PK_LEN];
+        data.copy_from_slice(&value[1..]);
+        PublicKey::key_validate(&data).map_err(|err| invalid_pk(format!("{err:?}")))?;
+        Ok(Self(data))
+    }
+}
+
+impl ToBaid58<BLS_PAYLOAD_LEN> for BlsPk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; BLS_PAYLOAD_LEN] {
+        let mut payload = [0u8; BLS_PAYLOAD_LEN];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0);
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for BlsPk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl BlsPk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+
+    fn public_key(&self) -> PublicKey {
+        PublicKey::from_bytes(&self.0).expect("public key is validated on construction")
+    }
+}
+
+impl Pk for BlsPk {
+    type Sk = BlsSk;
+    const ID: u8 = 8;
+
+    fn with(sk: &Self::Sk) -> Self { Self(sk.0.sk_to_pk().compress()) }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let Ok(sig) = Signature::from_bytes(&sig.0) else {
+            return false;
+        };
+        sig.verify(true, message.into().as_slice(), DST, &[], &self.public_key(), false)
+            == BLST_ERROR::BLST_SUCCESS
+    }
+
+    /// Verifies all signatures with a single multi-pairing, falling back to
+    /// individual verification only if the batch fails, to find invalid
+    /// entries.
+    fn verify_batch(batch: &[BatchItem<Self>]) -> Result<(), BatchVerifyError> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let mut sigs = Vec::with_capacity(batch.len());
+        for (_, _, sig) in batch {
+            match Signature::from_bytes(&sig.0) {
+                Ok(sig) => sigs.push(sig),
+                Err(_) => return verify_each(batch),
+            }
+        }
+        let pks = batch.iter().map(|(pk, _, _)| pk.public_key()).collect::<Vec<_>>();
+        let mut rng = thread_rng();
+        let rands = batch
+            .iter()
+            .map(|_| {
+                let mut b = [0u8; 32];
+                rng.fill(&mut b[..BATCH_RAND_BITS / 8]);
+                // Zero weight would exclude the signature from verification
+                b[0] |= 1;
+                blst_scalar { b }
+            })
+            .collect::<Vec<_>>();
+        let msgs = batch.iter().map(|(_, msg, _)| msg.as_slice()).collect::<Vec<_>>();
+        let pks = pks.iter().collect::<Vec<_>>();
+        let sigs = sigs.iter().collect::<Vec<_>>();
+        let res = Signature::verify_multiple_aggregate_signatures(
+            &msgs,
+            DST,
+            &pks,
+            false,
+            &sigs,
+            true,
+            &rands,
+            BATCH_RAND_BITS,
+        );
+        if res == BLST_ERROR::BLST_SUCCESS {
+            Ok(())
+        } else {
+            verify_each(batch)
+        }
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+impl StrictType for BlsPk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for BlsPk {}
+impl StrictTuple for BlsPk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for BlsPk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for BlsPk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; BLS_PK_LEN] = r.read_field()?;
+            PublicKey::key_validate(&data)
+                .map_err(|err| DecodeError::DataIntegrityError(format!("{err:?}")))?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for BlsPk {
+    fn strict_dumb() -> Self { Self(GENERATOR) }
+}
+
+/// Compressed BLS12-381 G2 signature.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct BlsSig([u8; BLS_SIG_LEN]);
+
+impl Sig for BlsSig {}
+
+impl StrictType for BlsSig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for BlsSig {}
+impl StrictTuple for BlsSig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for BlsSig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for BlsSig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; BLS_SIG_LEN] = r.read_field()?;
+            Signature::from_bytes(&data)
+                .map_err(|err| DecodeError::DataIntegrityError(format!("{err:?}")))?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for BlsSig {
+    fn strict_dumb() -> Self {
+        // Compressed point at infinity
+        let mut data = [0u8; BLS_SIG_LEN];
+        data[0] = 0xC0;
+        Self(data)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum BlsAggregateError {
+    /// no signatures to aggregate.
+    NoSignatures,
+
+    /// signature #{0} is made over a different digest than the rest of the
+    /// signatures.
+    DigestMismatch(usize),
+
+    /// signature #{0} is not a valid BLS signature.
+    InvalidSignature(usize),
+
+    /// signature #{0} is made with the same key as one of the previous
+    /// signatures.
+    DuplicateSigner(usize),
+
+    /// too many signers.
+    #[from(confinement::Error)]
+    TooManySigners,
+}
+
+/// Aggregate of BLS signatures made by several identities over the same
+/// digest, which is verified at once against all of the signer identities.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct BlsAggregateSig {
+    pub sig: SsiSignature<BlsSig>,
+    pub signers: SmallVec<IdCert<BlsPk>>,
+}
+
+impl StrictSerialize for BlsAggregateSig {}
+impl StrictDeserialize for BlsAggregateSig {}
+
+impl BlsAggregateSig {
+    /// Compresses co-signatures of the same digest into a single aggregate
+    /// signature.
+    ///
+    /// The individual signatures are not verified; use [`Self::verify`] on the
+    /// result.
+    pub fn aggregate(sigs: &[SigCert<BlsPk>]) -> Result<Self, BlsAggregateError> {
+        let first = sigs.first().ok_or(BlsAggregateError::NoSignatures)?;
+        let digest = first.sig.digest;
+        let mut points = Vec::with_capacity(sigs.len());
+        let mut keys = HashSet::with_capacity(sigs.len());
+        for (no, cert) in sigs.iter().enumerate() {
+            if cert.sig.digest != digest {
+                return Err(BlsAggregateError::DigestMismatch(no));
+            }
+            if !keys.insert(cert.identity().key) {
+                return Err(BlsAggregateError::DuplicateSigner(no));
+            }
+            points.push(
+                Signature::sig_validate(&cert.sig.sig.0, false)
+                    .map_err(|_| BlsAggregateError::InvalidSignature(no))?,
+            );
+        }
+        let points = points.iter().collect::<Vec<_>>();
+        let sig = AggregateSignature::aggregate(&points, false)
+            .expect("non-empty list of validated signatures")
+            .to_signature();
+        Ok(BlsAggregateSig {
+            sig: SsiSignature {
+                digest,
+                sig: BlsSig(sig.compress()),
+            },
+            signers: Confined::try_from_iter(sigs.iter().map(|cert| cert.id.clone()))?,
+        })
+    }
+
+    pub fn digest(&self) -> Bytes32 { self.sig.digest }
+
+    /// Checks that the aggregated signature is made by all of the signers over
+    /// the digest.
+    ///
+    /// Requires each signer to be a genesis identity with a valid self-signature,
+    /// which serves as the proof of possession of its key, and the keys of all
+    /// signers to be distinct, since a repeated key would count a single
+    /// co-signature several times.
+    #[must_use]
+    pub fn verify(&self) -> bool {
+        let mut pks = Vec::with_capacity(self.signers.len());
+        let mut keys = HashSet::with_capacity(self.signers.len());
+        for cert in &self.signers {
+            if !proves_possession(cert) || !keys.insert(cert.identity().key) {
+                return false;
+            }
+            pks.push(cert.identity().key.public_key());
+        }
+        let Ok(sig) = Signature::from_bytes(&self.sig.sig.0) else {
+            return false;
+        };
+        let pks = pks.iter().collect::<Vec<_>>();
+        sig.fast_aggregate_verify(true, self.sig.sig_hash().as_slice(), DST, &pks)
+            == BLST_ERROR::BLST_SUCCESS
+    }
+}
+
+/// Checks that the current key of the identity certificate has signed its own
+/// identity. Revocations are not signed by the new keys, so only genesis keys
+/// can prove the possession.
+fn proves_possession(cert: &IdCert<BlsPk>) -> bool {
+    cert.revocations.is_empty() && cert.verify().is_ok()
+}
+
+impl<C: BindleContent> Bindle<C, BlsPk> {
+    /// Compresses all co-signatures of the bindle into a single aggregate
+    /// signature.
+    pub fn aggregate_sigs(&self) -> Result<BlsAggregateSig, BlsAggregateError> {
+        BlsAggregateSig::aggregate(self.sigs())
+    }
+}
@@ -0,0 +1,225 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use secp256k1::{ecdh, ecdsa, Message, PublicKey, SecretKey, SECP256K1};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::Zeroizing;
+
+use super::{
+    check_pk_id, invalid_pk, parse_pk_payload, AgreementError, KeyAgreement, Pk, SharedSecret, Sig,
+};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+/// Compressed secp256k1 generator point, used as a dumb public key.
+const GENERATOR: [u8; 33] = [
+    0x02, 0x79, 0xBE, 0x66, 0x7E, 0xF9, 0xDC, 0xBB, 0xAC, 0x55, 0xA0, 0x62, 0x95, 0xCE, 0x87, 0x0B,
+    0x07, 0x02, 0x9B, 0xFC, 0xDB, 0x2D, 0xCE, 0x28, 0xD9, 0x59, 0xF2, 0x81, 0x5B, 0x16, 0xF8, 0x17,
+    0x98,
+];
+
+pub struct EcdsaSk(SecretKey);
+
+impl StrictType for EcdsaSk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for EcdsaSk {}
+impl StrictTuple for EcdsaSk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for EcdsaSk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(Zeroizing::new(self.0.secret_bytes()).deref())
+    }
+}
+impl StrictDecode for EcdsaSk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = Zeroizing::new(r.read_field::<[u8; 32]>()?);
+            SecretKey::from_slice(&data[..])
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for EcdsaSk {
+    fn strict_dumb() -> Self {
+        Self(SecretKey::from_slice(&[0xFAu8; 32]).expect("valid secret key"))
+    }
+}
+impl Drop for EcdsaSk {
+    fn drop(&mut self) { self.0.non_secure_erase() }
+}
+impl StrictSerialize for EcdsaSk {}
+impl StrictDeserialize for EcdsaSk {}
+
+impl Sk for EcdsaSk {
+    type Sig = EcdsaSig;
+
+    fn generate() -> Self { Self(SecretKey::new(&mut rand::thread_rng())) }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let msg = Message::from_digest(message.into().to_byte_array());
+        let mut sig = SECP256K1.sign_ecdsa(&msg, &self.0);
+        sig.normalize_s();
+        EcdsaSig(sig)
+    }
+}
+
+impl BindleContent for EcdsaSk {
+    const MAGIC: [u8; 4] = *b"SSKE";
+    const PLATE_TITLE: &'static str = "SSID ECDSA SECRET KEY";
+    type Id = EcdsaPk;
+
+    fn bindle_id(&self) -> Self::Id { EcdsaPk::with(self) }
+}
+
+/// Compressed secp256k1 public key used for ECDSA signatures.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct EcdsaPk(PublicKey);
+
+impl TryFrom<[u8; 34]> for EcdsaPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; 34]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        PublicKey::from_slice(&value[1..]).map(Self).map_err(invalid_pk)
+    }
+}
+
+impl ToBaid58<34> for EcdsaPk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; 34] {
+        let mut payload = [0u8; 34];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0.serialize());
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for EcdsaPk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl EcdsaPk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+impl Pk for EcdsaPk {
+    type Sk = EcdsaSk;
+    const ID: u8 = 3;
+
+    fn with(sk: &Self::Sk) -> Self { Self(sk.0.public_key(SECP256K1)) }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let msg = Message::from_digest(message.into().to_byte_array());
+        SECP256K1.verify_ecdsa(&msg, &sig.0, &self.0).is_ok()
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+/// ECDH over secp256k1, using the x-coordinate of the shared point as the
+/// shared secret, compatible with [`Secp256k1Pk`](super::Secp256k1Pk) key
+/// agreement.
+impl KeyAgreement for EcdsaPk {
+    fn agree(&self, sk: &Self::Sk) -> Result<SharedSecret, AgreementError> {
+        let point = Zeroizing::new(ecdh::shared_secret_point(&self.0, &sk.0));
+        let mut x = [0u8; 32];
+        x.copy_from_slice(&point[..32]);
+        SharedSecret::with(x)
+    }
+}
+
+impl StrictType for EcdsaPk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for EcdsaPk {}
+impl StrictTuple for EcdsaPk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for EcdsaPk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0.serialize())
+    }
+}
+impl StrictDecode for EcdsaPk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 33] = r.read_field()?;
+            PublicKey::from_slice(&data)
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for EcdsaPk {
+    fn strict_dumb() -> Self { Self(PublicKey::from_slice(&GENERATOR).expect("generator point")) }
+}
+
+/// ECDSA signature in compact 64-byte form with low-S normalization.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct EcdsaSig(ecdsa::Signature);
+
+impl Sig for EcdsaSig {}
+
+impl StrictType for EcdsaSig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for EcdsaSig {}
+impl StrictTuple for EcdsaSig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for EcdsaSig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0.serialize_compact())
+    }
+}
+impl StrictDecode for EcdsaSig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 64] = r.read_field()?;
+            let sig = ecdsa::Signature::from_compact(&data)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+            let mut normalized = sig;
+            normalized.normalize_s();
+            if normalized != sig {
+                return Err(DecodeError::DataIntegrityError(s!(
+                    "ECDSA signature is not low-S normalized"
+                )));
+            }
+            Ok(Self(sig))
+        })
+    }
+}
+impl StrictDumb for EcdsaSig {
+    fn strict_dumb() -> Self {
+        Self(ecdsa::Signature::from_compact(&[0x01u8; 64]).expect("valid signature"))
+    }
+}
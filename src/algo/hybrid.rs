@@ -0,0 +1,164 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Composite keys requiring both classical and post-quantum signatures to be valid.
+
+use std::str::FromStr;
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use strict_encoding::{StrictDeserialize, StrictDumb, StrictSerialize};
+
+use super::mldsa::MLDSA_PAYLOAD_LEN;
+use super::ristretto25519::RISTRETTO_PAYLOAD_LEN;
+use super::{
+    check_pk_id, parse_pk_payload, MlDsaPk, MlDsaSig, MlDsaSk, Pk, RistrettoPk, RistrettoSig,
+    RistrettoSk, Sig,
+};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+const CLASSIC_PAYLOAD_LEN: usize = RISTRETTO_PAYLOAD_LEN;
+const PQ_PAYLOAD_LEN: usize = MLDSA_PAYLOAD_LEN;
+/// Length of the baid58 payload of [`HybridPk`], including the key id.
+pub(crate) const HYBRID_PAYLOAD_LEN: usize = 1 + CLASSIC_PAYLOAD_LEN + PQ_PAYLOAD_LEN;
+
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct HybridSk {
+    pub classic: RistrettoSk,
+    pub pq: MlDsaSk,
+}
+
+impl StrictDumb for HybridSk {
+    fn strict_dumb() -> Self {
+        Self {
+            classic: strict_dumb!(),
+            pq: strict_dumb!(),
+        }
+    }
+}
+impl StrictSerialize for HybridSk {}
+impl StrictDeserialize for HybridSk {}
+
+impl Sk for HybridSk {
+    type Sig = HybridSig;
+
+    fn generate() -> Self {
+        Self {
+            classic: RistrettoSk::generate(),
+            pq: MlDsaSk::generate(),
+        }
+    }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let message: SigHash = message.into();
+        HybridSig {
+            classic: self.classic.sign(message),
+            pq: self.pq.sign(message),
+        }
+    }
+}
+
+impl BindleContent for HybridSk {
+    const MAGIC: [u8; 4] = *b"SSKY";
+    const PLATE_TITLE: &'static str = "SSID HYBRID SECRET KEY";
+    type Id = HybridPk;
+
+    fn bindle_id(&self) -> Self::Id { HybridPk::with(self) }
+}
+
+/// Composite public key made of [`RistrettoPk`] and post-quantum [`MlDsaPk`].
+///
+/// A signature is valid only if the signatures by both of the component keys
+/// are valid, so the key remains secure for as long as at least one of the
+/// primitives is not broken.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+#[display(Self::to_baid58_string)]
+pub struct HybridPk {
+    pub classic: RistrettoPk,
+    pub pq: MlDsaPk,
+}
+
+impl TryFrom<[u8; HYBRID_PAYLOAD_LEN]> for HybridPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; HYBRID_PAYLOAD_LEN]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        let mut classic = [0u8; CLASSIC_PAYLOAD_LEN];
+        let mut pq = [0u8; PQ_PAYLOAD_LEN];
+        classic.copy_from_slice(&value[1..=CLASSIC_PAYLOAD_LEN]);
+        pq.copy_from_slice(&value[1 + CLASSIC_PAYLOAD_LEN..]);
+        Ok(Self {
+            classic: RistrettoPk::try_from(classic)?,
+            pq: MlDsaPk::try_from(pq)?,
+        })
+    }
+}
+
+impl ToBaid58<HYBRID_PAYLOAD_LEN> for HybridPk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; HYBRID_PAYLOAD_LEN] {
+        let mut payload = [0u8; HYBRID_PAYLOAD_LEN];
+        payload[0] = Self::ID;
+        payload[1..=CLASSIC_PAYLOAD_LEN].copy_from_slice(&self.classic.to_baid58_payload());
+        payload[1 + CLASSIC_PAYLOAD_LEN..].copy_from_slice(&self.pq.to_baid58_payload());
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for HybridPk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl HybridPk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+impl Pk for HybridPk {
+    type Sk = HybridSk;
+    const ID: u8 = 7;
+
+    fn with(sk: &Self::Sk) -> Self {
+        Self {
+            classic: RistrettoPk::with(&sk.classic),
+            pq: MlDsaPk::with(&sk.pq),
+        }
+    }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let message: SigHash = message.into();
+        self.classic.verify(message, &sig.classic) && self.pq.verify(message, &sig.pq)
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+/// Pair of signatures made by both components of a [`HybridPk`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct HybridSig {
+    pub classic: RistrettoSig,
+    pub pq: MlDsaSig,
+}
+
+impl Sig for HybridSig {}
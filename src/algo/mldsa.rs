@@ -0,0 +1,220 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Post-quantum lattice-based ML-DSA-65 (FIPS 204, formerly Dilithium) keys.
+
+use std::str::FromStr;
+use std::{io, mem, slice};
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use pqcrypto_mldsa::mldsa65;
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::Zeroize;
+
+use super::{check_pk_id, parse_pk_payload, Pk, Sig};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+const MLDSA_PK_LEN: usize = 1952;
+const MLDSA_SK_LEN: usize = 4032;
+const MLDSA_SIG_LEN: usize = 3309;
+/// Length of the baid58 payload of [`MlDsaPk`], including the key id.
+pub(crate) const MLDSA_PAYLOAD_LEN: usize = MLDSA_PK_LEN + 1;
+
+/// ML-DSA secret key.
+///
+/// ML-DSA doesn't allow cheap re-computation of the public key from the
+/// secret key, thus the public key is kept alongside.
+#[derive(StrictType, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct MlDsaSk {
+    sk: [u8; MLDSA_SK_LEN],
+    pk: MlDsaPk,
+}
+
+impl StrictDumb for MlDsaSk {
+    fn strict_dumb() -> Self {
+        Self {
+            sk: [0xFAu8; MLDSA_SK_LEN],
+            pk: strict_dumb!(),
+        }
+    }
+}
+impl Drop for MlDsaSk {
+    fn drop(&mut self) { self.sk.zeroize() }
+}
+impl StrictSerialize for MlDsaSk {}
+impl StrictDeserialize for MlDsaSk {}
+
+/// Copy of the secret key in the [`pqcrypto_mldsa`] format, which is wiped on
+/// drop.
+struct SkCopy(mldsa65::SecretKey);
+
+impl Drop for SkCopy {
+    fn drop(&mut self) {
+        // `mldsa65::SecretKey` is a plain byte array providing no mutable access
+        // to its data
+        let data = unsafe {
+            slice::from_raw_parts_mut(
+                &mut self.0 as *mut mldsa65::SecretKey as *mut u8,
+                mem::size_of::<mldsa65::SecretKey>(),
+            )
+        };
+        data.zeroize();
+    }
+}
+
+impl Sk for MlDsaSk {
+    type Sig = MlDsaSig;
+
+    fn generate() -> Self {
+        let (pk, sk) = mldsa65::keypair();
+        let sk = SkCopy(sk);
+        let mut me = Self {
+            sk: [0u8; MLDSA_SK_LEN],
+            pk: MlDsaPk([0u8; MLDSA_PK_LEN]),
+        };
+        me.sk.copy_from_slice(sk.0.as_bytes());
+        me.pk.0.copy_from_slice(pk.as_bytes());
+        me
+    }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let sk = SkCopy(mldsa65::SecretKey::from_bytes(&self.sk).expect("fixed length"));
+        let sig = mldsa65::detached_sign(message.into().as_slice(), &sk.0);
+        let mut data = [0u8; MLDSA_SIG_LEN];
+        data.copy_from_slice(sig.as_bytes());
+        MlDsaSig(data)
+    }
+}
+
+impl BindleContent for MlDsaSk {
+    const MAGIC: [u8; 4] = *b"SSKM";
+    const PLATE_TITLE: &'static str = "SSID ML-DSA SECRET KEY";
+    type Id = MlDsaPk;
+
+    fn bindle_id(&self) -> Self::Id { MlDsaPk::with(self) }
+}
+
+/// ML-DSA-65 public key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct MlDsaPk([u8; MLDSA_PK_LEN]);
+
+impl TryFrom<[u8; MLDSA_PAYLOAD_LEN]> for MlDsaPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; MLDSA_PAYLOAD_LEN]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        let mut data = [0u8; MLDSA_PK_LEN];
+        data.copy_from_slice(&value[1..]);
+        Ok(Self(data))
+    }
+}
+
+impl ToBaid58<MLDSA_PAYLOAD_LEN> for MlDsaPk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; MLDSA_PAYLOAD_LEN] {
+        let mut payload = [0u8; MLDSA_PAYLOAD_LEN];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0);
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for MlDsaPk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl MlDsaPk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+impl Pk for MlDsaPk {
+    type Sk = MlDsaSk;
+    const ID: u8 = 5;
+
+    fn with(sk: &Self::Sk) -> Self { sk.pk }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let pk = mldsa65::PublicKey::from_bytes(&self.0).expect("fixed length");
+        let sig = mldsa65::DetachedSignature::from_bytes(&sig.0).expect("fixed length");
+        mldsa65::verify_detached_signature(&sig, message.into().as_slice(), &pk).is_ok()
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+impl StrictType for MlDsaPk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for MlDsaPk {}
+impl StrictTuple for MlDsaPk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for MlDsaPk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for MlDsaPk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = r.read_field()?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for MlDsaPk {
+    fn strict_dumb() -> Self { Self([0xFAu8; MLDSA_PK_LEN]) }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct MlDsaSig([u8; MLDSA_SIG_LEN]);
+
+impl Sig for MlDsaSig {}
+
+impl StrictType for MlDsaSig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for MlDsaSig {}
+impl StrictTuple for MlDsaSig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for MlDsaSig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for MlDsaSig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = r.read_field()?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for MlDsaSig {
+    fn strict_dumb() -> Self { Self([0xFAu8; MLDSA_SIG_LEN]) }
+}
@@ -20,25 +20,140 @@
 // limitations under the License.
 
 mod ristretto25519;
+mod bip340;
+mod ecdsa;
+mod any;
+#[cfg(feature = "p256")]
+mod nistp256;
+#[cfg(feature = "mldsa")]
+mod mldsa;
+#[cfg(feature = "mldsa")]
+mod hybrid;
+#[cfg(feature = "slhdsa")]
+mod slhdsa;
+#[cfg(feature = "bls")]
+mod bls12381;
 
 use std::fmt::{Debug, Display};
+use std::io;
+use std::ops::Deref;
 use std::str::FromStr;
 
+use amplify::confinement::U16;
 use amplify::Bytes4;
-use baid58::Baid58ParseError;
+pub use any::{AnyPk, AnySig, AnySk};
+use baid58::{Baid58ParseError, Chunking, FromBaid58, ToBaid58, CHUNKING_32};
+pub use bip340::{Secp256k1Pk, Secp256k1Sig, Secp256k1Sk};
+#[cfg(feature = "bls")]
+pub use bls12381::{BlsAggregateError, BlsAggregateSig, BlsPk, BlsSig, BlsSk};
+pub use ecdsa::{EcdsaPk, EcdsaSig, EcdsaSk};
+#[cfg(feature = "mldsa")]
+pub use hybrid::{HybridPk, HybridSig, HybridSk};
+#[cfg(feature = "mldsa")]
+pub use mldsa::{MlDsaPk, MlDsaSig, MlDsaSk};
+#[cfg(feature = "p256")]
+pub use nistp256::{P256Pk, P256Sig, P256Sk};
 pub use ristretto25519::{RistrettoPk, RistrettoSig, RistrettoSk};
-use strict_encoding::{StrictDecode, StrictDumb, StrictEncode, StrictType};
+#[cfg(feature = "slhdsa")]
+pub use slhdsa::{SlhDsaPk, SlhDsaSig, SlhDsaSk};
+use strict_encoding::{
+    StrictDecode, StrictDumb, StrictEncode, StrictSerialize, StrictType, TypedWrite,
+};
+use zeroize::Zeroizing;
 
-use crate::{BindleContent, Digest};
+use crate::{BindleContent, LongFingerprint, SigHash, LIB_NAME_SSID};
 
+/// Short fingerprint of a key, used as a human-readable alias only.
+///
+/// It consists of the first bytes of the [`LongFingerprint`] hash. Short
+/// fingerprints are not collision-resistant and can be brute-forced;
+/// use [`LongFingerprint`] for matching identities.
 pub type Fingerprint = Bytes4;
 
+/// Public key, signed message hash and the signature being checked as a part
+/// of a batch verification.
+pub type BatchItem<K> = (K, SigHash, <<K as Pk>::Sk as Sk>::Sig);
+
+#[derive(Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+/// invalid signatures in batch entries {invalid:?}.
+pub struct BatchVerifyError {
+    /// Indexes of the failed entries in the batch, in ascending order.
+    pub invalid: Vec<usize>,
+}
+
+impl BatchVerifyError {
+    pub(crate) fn check(invalid: Vec<usize>) -> Result<(), Self> {
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(BatchVerifyError { invalid })
+        }
+    }
+}
+
+/// Verifies each of the batch entries one by one.
+pub(crate) fn verify_each<K: Pk>(batch: &[BatchItem<K>]) -> Result<(), BatchVerifyError> {
+    let invalid = batch
+        .iter()
+        .enumerate()
+        .filter(|(_, (pk, message, sig))| !pk.verify(*message, sig))
+        .map(|(no, _)| no)
+        .collect();
+    BatchVerifyError::check(invalid)
+}
+
+/// Raw baid58 payload of a public key. All public keys share the same HRI, so
+/// the payload is parsed first and the key id is checked before the key itself.
+pub(crate) struct PkPayload<const LEN: usize>([u8; LEN]);
+
+impl<const LEN: usize> From<[u8; LEN]> for PkPayload<LEN> {
+    fn from(value: [u8; LEN]) -> Self { Self(value) }
+}
+impl<const LEN: usize> ToBaid58<LEN> for PkPayload<LEN> {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; LEN] { self.0 }
+}
+impl<const LEN: usize> FromBaid58<LEN> for PkPayload<LEN> {}
+
+pub(crate) fn parse_pk_payload<const LEN: usize>(s: &str) -> Result<[u8; LEN], Baid58ParseError> {
+    PkPayload::<LEN>::from_baid58_chunked_str(s, ':', '#').map(|payload| payload.0)
+}
+
+/// Checks that the baid58 payload holds a key with the given algorithm id.
+pub(crate) fn check_pk_id(payload: &[u8], id: u8) -> Result<(), Baid58ParseError> {
+    match payload.first() {
+        Some(found) if *found == id => Ok(()),
+        Some(found) => Err(invalid_pk(format!("key id {found} where {id} is expected"))),
+        None => Err(invalid_pk("empty key data")),
+    }
+}
+
+pub(crate) fn invalid_pk(details: impl ToString) -> Baid58ParseError {
+    Baid58ParseError::Unparsable(details.to_string())
+}
+
+/// Wrapper allowing to serialize public keys, which don't implement
+/// [`StrictSerialize`] themselves.
+struct KeyData<'a, K: Pk>(&'a K);
+
+impl<'a, K: Pk> StrictType for KeyData<'a, K> {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl<'a, K: Pk> StrictEncode for KeyData<'a, K> {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        self.0.strict_encode(writer)
+    }
+}
+impl<'a, K: Pk> StrictSerialize for KeyData<'a, K> {}
+
 pub trait Sk: BindleContent {
     type Sig: Sig;
 
     fn generate() -> Self;
 
-    fn sign(&self, message: impl Into<Digest>) -> Self::Sig;
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig;
 }
 
 pub trait Pk:
@@ -58,9 +173,91 @@ pub trait Pk:
     fn with(sk: &Self::Sk) -> Self;
 
     #[must_use]
-    fn verify(&self, message: impl Into<Digest>, sig: &<Self::Sk as Sk>::Sig) -> bool;
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool;
+
+    /// Verifies many signatures at once, reporting all the failed entries.
+    ///
+    /// Algorithms supporting batch verification override this method; the
+    /// default implementation verifies each entry separately.
+    fn verify_batch(batch: &[BatchItem<Self>]) -> Result<(), BatchVerifyError> {
+        verify_each(batch)
+    }
+
+    /// Checksum of the key encoded as words, which may be used to compare keys
+    /// by reading them out loud.
+    ///
+    /// The default implementation takes the mnemonic suffix (following `#`) of
+    /// the baid58 string representation of the key.
+    fn to_mnemonic(&self) -> String {
+        self.to_string().rsplit_once('#').map(|(_, words)| words.to_owned()).unwrap_or_default()
+    }
+
+    /// Serialized key data, which is committed to by the signed identity
+    /// hashes and the fingerprints.
+    ///
+    /// Runtime-dispatched keys return the data of the underlying key, so the
+    /// hashes don't depend on whether the key type is known at compile time.
+    fn to_key_data(&self) -> Vec<u8> {
+        KeyData(self)
+            .to_strict_serialized::<U16>()
+            .expect("serialized public key does not fit 64 kB")
+            .release()
+    }
+
+    /// Short fingerprint of the key, which is a prefix of the hash of its
+    /// [`Pk::long_fingerprint`].
+    fn fingerprint(&self) -> Fingerprint { self.long_fingerprint().to_short() }
+
+    /// Collision-resistant fingerprint of the key.
+    fn long_fingerprint(&self) -> LongFingerprint { LongFingerprint::with_key(Self::ID, self) }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum AgreementError {
+    /// public key can't be used for the key agreement.
+    InvalidPk,
+
+    /// key agreement resulted in a weak shared secret.
+    WeakSecret,
+
+    /// keys of different algorithms can't be used for the key agreement.
+    AlgoMismatch,
+
+    /// key algorithm doesn't support key agreement.
+    Unsupported,
+}
+
+/// Raw output of a Diffie-Hellman key agreement, which is erased from the
+/// memory on drop.
+///
+/// The value is not uniformly random and must be passed through a key
+/// derivation function before being used as an encryption key.
+pub struct SharedSecret(Zeroizing<[u8; 32]>);
+
+impl Deref for SharedSecret {
+    type Target = [u8; 32];
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl SharedSecret {
+    pub(crate) fn with(data: [u8; 32]) -> Result<Self, AgreementError> {
+        let secret = Self(Zeroizing::new(data));
+        if secret.iter().all(|byte| *byte == 0) {
+            return Err(AgreementError::WeakSecret);
+        }
+        Ok(secret)
+    }
+
+    pub fn as_slice(&self) -> &[u8] { self.0.as_slice() }
+}
 
-    fn fingerprint(&self) -> Fingerprint;
+/// Public key supporting Diffie-Hellman key agreement with secret keys of the
+/// same algorithm, allowing to encrypt data to an identity.
+pub trait KeyAgreement: Pk {
+    /// Computes the secret shared between the owner of `sk` and the owner of
+    /// this public key.
+    fn agree(&self, sk: &Self::Sk) -> Result<SharedSecret, AgreementError>;
 }
 
 pub trait Sig: Copy + Eq + Debug + StrictType + StrictDumb + StrictEncode + StrictDecode {}
@@ -0,0 +1,224 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use p256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{check_pk_id, invalid_pk, parse_pk_payload, Pk, Sig};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+/// Compressed NIST P-256 generator point, used as a dumb public key.
+const GENERATOR: [u8; 33] = [
+    0x03, 0x6B, 0x17, 0xD1, 0xF2, 0xE1, 0x2C, 0x42, 0x47, 0xF8, 0xBC, 0xE6, 0xE5, 0x63, 0xA4, 0x40,
+    0xF2, 0x77, 0x03, 0x7D, 0x81, 0x2D, 0xEB, 0x33, 0xA0, 0xF4, 0xA1, 0x39, 0x45, 0xD8, 0x98, 0xC2,
+    0x96,
+];
+
+pub struct P256Sk(SigningKey);
+
+impl StrictType for P256Sk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for P256Sk {}
+impl StrictTuple for P256Sk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for P256Sk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let mut bytes = self.0.to_bytes();
+        let mut data = Zeroizing::new([0u8; 32]);
+        data.copy_from_slice(&bytes);
+        bytes.as_mut_slice().zeroize();
+        writer.write_newtype::<Self>(data.deref())
+    }
+}
+impl StrictDecode for P256Sk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = Zeroizing::new(r.read_field::<[u8; 32]>()?);
+            SigningKey::from_slice(&data[..])
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for P256Sk {
+    fn strict_dumb() -> Self {
+        Self(SigningKey::from_slice(&[0xFAu8; 32]).expect("valid secret key"))
+    }
+}
+impl StrictSerialize for P256Sk {}
+impl StrictDeserialize for P256Sk {}
+
+impl Sk for P256Sk {
+    type Sig = P256Sig;
+
+    fn generate() -> Self { Self(SigningKey::random(&mut rand::thread_rng())) }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let sig: Signature = self
+            .0
+            .sign_prehash(message.into().as_slice())
+            .expect("32-byte digest is a valid prehash");
+        let sig = sig.normalize_s().unwrap_or(sig);
+        P256Sig(sig.to_bytes().into())
+    }
+}
+
+impl BindleContent for P256Sk {
+    const MAGIC: [u8; 4] = *b"SSKP";
+    const PLATE_TITLE: &'static str = "SSID P256 SECRET KEY";
+    type Id = P256Pk;
+
+    fn bindle_id(&self) -> Self::Id { P256Pk::with(self) }
+}
+
+/// Compressed SEC1 encoding of a NIST P-256 (secp256r1) public key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct P256Pk([u8; 33]);
+
+impl TryFrom<[u8; 34]> for P256Pk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; 34]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        let mut data = [0u8; 33];
+        data.copy_from_slice(&value[1..]);
+        VerifyingKey::from_sec1_bytes(&data).map_err(invalid_pk)?;
+        Ok(Self(data))
+    }
+}
+
+impl ToBaid58<34> for P256Pk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; 34] {
+        let mut payload = [0u8; 34];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0);
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for P256Pk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl P256Pk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+
+    fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey::from_sec1_bytes(&self.0).expect("public key is validated on construction")
+    }
+}
+
+impl Pk for P256Pk {
+    type Sk = P256Sk;
+    const ID: u8 = 4;
+
+    fn with(sk: &Self::Sk) -> Self {
+        let mut data = [0u8; 33];
+        data.copy_from_slice(sk.0.verifying_key().to_encoded_point(true).as_bytes());
+        Self(data)
+    }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let Ok(sig) = Signature::from_slice(&sig.0) else {
+            return false;
+        };
+        self.verifying_key().verify_prehash(message.into().as_slice(), &sig).is_ok()
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+impl StrictType for P256Pk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for P256Pk {}
+impl StrictTuple for P256Pk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for P256Pk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for P256Pk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 33] = r.read_field()?;
+            VerifyingKey::from_sec1_bytes(&data)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for P256Pk {
+    fn strict_dumb() -> Self { Self(GENERATOR) }
+}
+
+/// ECDSA signature over NIST P-256 in compact 64-byte form with low-S normalization.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct P256Sig([u8; 64]);
+
+impl Sig for P256Sig {}
+
+impl StrictType for P256Sig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for P256Sig {}
+impl StrictTuple for P256Sig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for P256Sig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for P256Sig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; 64] = r.read_field()?;
+            let sig = Signature::from_slice(&data)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+            if sig.normalize_s().is_some() {
+                return Err(DecodeError::DataIntegrityError(s!(
+                    "P-256 signature is not low-S normalized"
+                )));
+            }
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for P256Sig {
+    fn strict_dumb() -> Self { Self([0x01u8; 64]) }
+}
@@ -19,22 +19,36 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::io;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::str::FromStr;
 
-use baid58::{Baid58ParseError, Chunking, FromBaid58, ToBaid58, CHUNKING_32};
-use ec25519::{Noise, PublicKey, SecretKey, Signature};
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+#[cfg(any(feature = "vrf", feature = "ring"))]
+use curve25519_dalek::scalar::{clamp_integer, Scalar};
+use ec25519::{x25519, KeyPair, Noise, PublicKey, SecretKey, Seed, Signature};
 use rand::{random, thread_rng, Rng};
+#[cfg(any(feature = "vrf", feature = "ring"))]
+use sha2::{Digest, Sha512};
 use strict_encoding::{
     DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
     StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
 };
+use zeroize::{Zeroize, Zeroizing};
 
-use super::{Fingerprint, Pk, Sig};
-use crate::{BindleContent, Digest, Sk, LIB_NAME_SSID};
+use super::{check_pk_id, parse_pk_payload, AgreementError, KeyAgreement, Pk, SharedSecret, Sig};
+use crate::{BindleContent, DerivationPath, SigHash, Sk, LIB_NAME_SSID};
 
-pub struct RistrettoSk(SecretKey);
+/// Length of the baid58 payload of [`RistrettoPk`], including the key id.
+pub(crate) const RISTRETTO_PAYLOAD_LEN: usize = 33;
+
+/// Ed25519 secret key, optionally accompanied by the SLIP-10 derivation path
+/// from the master seed it was derived with.
+///
+/// The derivation path is not a part of the serialized key; it is kept in
+/// memory only and is carried by the `Derivation` header of the bindle.
+pub struct RistrettoSk(SecretKey, Option<DerivationPath>);
 
 impl StrictType for RistrettoSk {
     const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
@@ -51,13 +65,19 @@ impl StrictEncode for RistrettoSk {
 impl StrictDecode for RistrettoSk {
     fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
         reader.read_tuple(|r| {
-            let data = r.read_field()?;
-            Ok(Self(SecretKey::new(data)))
+            let mut data: [u8; 64] = r.read_field()?;
+            let sk = SecretKey::new(data);
+            data.zeroize();
+            Ok(Self(sk, None))
         })
     }
 }
 impl StrictDumb for RistrettoSk {
-    fn strict_dumb() -> Self { Self(SecretKey::new([0xFAu8; 64])) }
+    fn strict_dumb() -> Self { Self(SecretKey::new([0xFAu8; 64]), None) }
+}
+impl Drop for RistrettoSk {
+    // `ec25519::SecretKey` wipes only a copy of its data on drop
+    fn drop(&mut self) { self.0.deref_mut().zeroize() }
 }
 impl StrictSerialize for RistrettoSk {}
 impl StrictDeserialize for RistrettoSk {}
@@ -66,56 +86,119 @@ impl Sk for RistrettoSk {
     type Sig = RistrettoSig;
 
     fn generate() -> Self {
-        let mut data = [0u8; 64];
-        thread_rng().fill(&mut data);
-        Self(SecretKey::new(data))
+        let mut seed = Zeroizing::new([0u8; 32]);
+        thread_rng().fill(&mut *seed);
+        Self::from_seed(*seed)
     }
 
-    fn sign(&self, message: impl Into<Digest>) -> Self::Sig {
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
         RistrettoSig(self.0.sign(message.into(), Some(Noise::new(random()))))
     }
 }
 
+impl RistrettoSk {
+    /// Constructs secret key from a 32-byte RFC 8032 seed.
+    ///
+    /// # Panics
+    ///
+    /// If the seed consists of zero bytes only.
+    pub fn from_seed(seed: [u8; 32]) -> Self { Self(KeyPair::from_seed(Seed::new(seed)).sk, None) }
+
+    /// Records the SLIP-10 derivation path which was used to derive the key.
+    pub(crate) fn with_derivation(mut self, derivation: DerivationPath) -> Self {
+        self.1 = Some(derivation);
+        self
+    }
+
+    /// Returns SLIP-10 derivation path of the key, if the key was derived from
+    /// a master seed.
+    pub fn derivation(&self) -> Option<&DerivationPath> { self.1.as_ref() }
+
+    /// Expands the key seed according to RFC 8032, returning the secret scalar
+    /// and the nonce prefix.
+    #[cfg(any(feature = "vrf", feature = "ring"))]
+    pub(crate) fn to_expanded(&self) -> (Zeroizing<Scalar>, Zeroizing<[u8; 32]>) {
+        let mut expanded = Zeroizing::new([0u8; 64]);
+        let mut hasher = Sha512::new();
+        hasher.update(&self.0[..32]);
+        hasher.finalize_into(expanded.as_mut_slice().into());
+        let mut scalar = Zeroizing::new([0u8; 32]);
+        let mut prefix = Zeroizing::new([0u8; 32]);
+        scalar.copy_from_slice(&expanded[..32]);
+        prefix.copy_from_slice(&expanded[32..]);
+        let scalar = Zeroizing::new(Scalar::from_bytes_mod_order(clamp_integer(*scalar)));
+        (scalar, prefix)
+    }
+
+    /// Signs the message according to RFC 8032 without adding any noise to the
+    /// nonce, such that the same key and message always produce the same
+    /// signature.
+    ///
+    /// Prefer [`Sk::sign`] unless the reproducibility is required, since the
+    /// noise protects against fault attacks.
+    pub fn sign_deterministic(&self, message: impl Into<SigHash>) -> RistrettoSig {
+        self.sign_bytes_deterministic(message.into().as_slice())
+    }
+
+    fn sign_bytes_deterministic(&self, message: &[u8]) -> RistrettoSig {
+        RistrettoSig(self.0.sign(message, None))
+    }
+}
+
 impl BindleContent for RistrettoSk {
     const MAGIC: [u8; 4] = *b"SSSK";
     const PLATE_TITLE: &'static str = "SSID SECRET KEY";
     type Id = RistrettoPk;
 
     fn bindle_id(&self) -> Self::Id { RistrettoPk::with(self) }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        match &self.1 {
+            Some(derivation) => bmap! { "Derivation" => derivation.to_string() },
+            None => none!(),
+        }
+    }
+    fn restore_bindle_headers(&mut self, headers: &BTreeMap<&str, &str>) -> Result<(), String> {
+        if let Some(derivation) = headers.get("Derivation") {
+            let derivation = DerivationPath::from_str(derivation).map_err(|e| e.to_string())?;
+            self.1 = Some(derivation);
+        }
+        Ok(())
+    }
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
 #[display(Self::to_baid58_string)]
 pub struct RistrettoPk(PublicKey);
 
-impl From<[u8; 33]> for RistrettoPk {
-    fn from(value: [u8; 33]) -> Self {
-        assert_eq!(value[0], Self::ID, "invalid key type");
+impl TryFrom<[u8; RISTRETTO_PAYLOAD_LEN]> for RistrettoPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; RISTRETTO_PAYLOAD_LEN]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
         let mut data = [0u8; 32];
         data.copy_from_slice(&value[1..]);
-        Self(PublicKey::new(data))
+        Ok(Self(PublicKey::new(data)))
     }
 }
 
-impl ToBaid58<33> for RistrettoPk {
+impl ToBaid58<RISTRETTO_PAYLOAD_LEN> for RistrettoPk {
     const HRI: &'static str = "ssi";
     const CHUNKING: Option<Chunking> = CHUNKING_32;
-    fn to_baid58_payload(&self) -> [u8; 33] {
-        let mut payload = [0u8; 33];
+    fn to_baid58_payload(&self) -> [u8; RISTRETTO_PAYLOAD_LEN] {
+        let mut payload = [0u8; RISTRETTO_PAYLOAD_LEN];
         payload[0] = Self::ID;
         payload[1..].copy_from_slice(self.0.deref());
         payload
     }
     fn to_baid58_string(&self) -> String { self.to_string() }
 }
-impl FromBaid58<33> for RistrettoPk {}
 impl FromStr for RistrettoPk {
     type Err = Baid58ParseError;
-    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid58_chunked_str(s, ':', '#') }
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
 }
 impl RistrettoPk {
     pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
-    pub fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+    pub fn from_byte_array(data: [u8; 32]) -> Self { Self(PublicKey::new(data)) }
+    pub fn to_byte_array(&self) -> [u8; 32] { *self.0 }
 }
 
 impl Pk for RistrettoPk {
@@ -124,12 +207,20 @@ impl Pk for RistrettoPk {
 
     fn with(sk: &Self::Sk) -> Self { Self(sk.0.public_key()) }
 
-    fn verify(&self, message: impl Into<Digest>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
         self.0.verify(message.into(), &sig.0).is_ok()
     }
 
-    fn fingerprint(&self) -> Fingerprint {
-        Fingerprint::copy_from_slice(&self.0[0..4]).expect("fixed length")
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+/// X25519 key agreement using the Montgomery form of the Ed25519 keys.
+impl KeyAgreement for RistrettoPk {
+    fn agree(&self, sk: &Self::Sk) -> Result<SharedSecret, AgreementError> {
+        let pk = x25519::PublicKey::from_ed25519(&self.0).map_err(|_| AgreementError::InvalidPk)?;
+        let sk = x25519::SecretKey::from_ed25519(&sk.0).expect("valid Ed25519 secret key");
+        let shared = pk.dh(&sk).map_err(|_| AgreementError::WeakSecret)?;
+        SharedSecret::with(*shared)
     }
 }
 
@@ -162,6 +253,11 @@ pub struct RistrettoSig(Signature);
 
 impl Sig for RistrettoSig {}
 
+impl RistrettoSig {
+    pub fn from_byte_array(data: [u8; 64]) -> Self { Self(Signature::new(data)) }
+    pub fn to_byte_array(&self) -> [u8; 64] { *self.0 }
+}
+
 impl StrictType for RistrettoSig {
     const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
 }
@@ -185,3 +281,44 @@ impl StrictDecode for RistrettoSig {
 impl StrictDumb for RistrettoSig {
     fn strict_dumb() -> Self { Self(Signature::new([0xFAu8; 64])) }
 }
+
+#[cfg(test)]
+mod test {
+    use amplify::hex::FromHex;
+
+    use super::*;
+
+    /// Test vectors from RFC 8032 section 7.1: seed, message and signature.
+    const RFC8032: [(&str, &str, &str); 3] = [
+        (
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+            "",
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        ),
+        (
+            "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+            "72",
+            "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+             085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+        ),
+        (
+            "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+            "af82",
+            "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac\
+             18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+        ),
+    ];
+
+    #[test]
+    fn rfc8032_signatures() {
+        for (seed, message, sig) in RFC8032 {
+            let sk = RistrettoSk::from_seed(<[u8; 32]>::from_hex(seed).unwrap());
+            let message = Vec::<u8>::from_hex(message).unwrap();
+            assert_eq!(
+                sk.sign_bytes_deterministic(&message).to_byte_array(),
+                <[u8; 64]>::from_hex(sig).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,212 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stateless hash-based SLH-DSA-SHA2-128s (FIPS 205, formerly SPHINCS+) keys.
+
+use std::io;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use baid58::{Baid58ParseError, Chunking, ToBaid58, CHUNKING_32};
+use signature::{Signer, Verifier};
+use slh_dsa::{Sha2_128s, Signature, SigningKey, VerifyingKey};
+use strict_encoding::{
+    DecodeError, ReadTuple, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode,
+    StrictProduct, StrictSerialize, StrictTuple, StrictType, TypedRead, TypedWrite,
+};
+use zeroize::{Zeroize, Zeroizing};
+
+use super::{check_pk_id, invalid_pk, parse_pk_payload, Pk, Sig};
+use crate::{BindleContent, SigHash, Sk, LIB_NAME_SSID};
+
+const SLHDSA_PK_LEN: usize = 32;
+const SLHDSA_SK_LEN: usize = 64;
+const SLHDSA_SIG_LEN: usize = 7856;
+
+pub struct SlhDsaSk(SigningKey<Sha2_128s>);
+
+impl StrictType for SlhDsaSk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for SlhDsaSk {}
+impl StrictTuple for SlhDsaSk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for SlhDsaSk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        let mut bytes = self.0.to_bytes();
+        let mut data = Zeroizing::new([0u8; SLHDSA_SK_LEN]);
+        data.copy_from_slice(&bytes);
+        bytes.as_mut_slice().zeroize();
+        writer.write_newtype::<Self>(data.deref())
+    }
+}
+impl StrictDecode for SlhDsaSk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = Zeroizing::new(r.read_field::<[u8; SLHDSA_SK_LEN]>()?);
+            SigningKey::try_from(&data[..])
+                .map(Self)
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))
+        })
+    }
+}
+impl StrictDumb for SlhDsaSk {
+    fn strict_dumb() -> Self {
+        Self(SigningKey::try_from(&[0xFAu8; SLHDSA_SK_LEN][..]).expect("fixed length"))
+    }
+}
+impl StrictSerialize for SlhDsaSk {}
+impl StrictDeserialize for SlhDsaSk {}
+
+impl Sk for SlhDsaSk {
+    type Sig = SlhDsaSig;
+
+    fn generate() -> Self { Self(SigningKey::new(&mut rand::thread_rng())) }
+
+    fn sign(&self, message: impl Into<SigHash>) -> Self::Sig {
+        let sig: Signature<Sha2_128s> = self.0.sign(message.into().as_slice());
+        let mut data = [0u8; SLHDSA_SIG_LEN];
+        data.copy_from_slice(&sig.to_bytes());
+        SlhDsaSig(data)
+    }
+}
+
+impl BindleContent for SlhDsaSk {
+    const MAGIC: [u8; 4] = *b"SSKH";
+    const PLATE_TITLE: &'static str = "SSID SLH-DSA SECRET KEY";
+    type Id = SlhDsaPk;
+
+    fn bindle_id(&self) -> Self::Id { SlhDsaPk::with(self) }
+}
+
+/// SLH-DSA-SHA2-128s public key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(Self::to_baid58_string)]
+pub struct SlhDsaPk([u8; SLHDSA_PK_LEN]);
+
+impl TryFrom<[u8; 33]> for SlhDsaPk {
+    type Error = Baid58ParseError;
+    fn try_from(value: [u8; 33]) -> Result<Self, Self::Error> {
+        check_pk_id(&value, Self::ID)?;
+        VerifyingKey::<Sha2_128s>::try_from(&value[1..]).map_err(invalid_pk)?;
+        let mut data = [0u8; SLHDSA_PK_LEN];
+        data.copy_from_slice(&value[1..]);
+        Ok(Self(data))
+    }
+}
+
+impl ToBaid58<33> for SlhDsaPk {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; 33] {
+        let mut payload = [0u8; 33];
+        payload[0] = Self::ID;
+        payload[1..].copy_from_slice(&self.0);
+        payload
+    }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromStr for SlhDsaPk {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::try_from(parse_pk_payload(s)?) }
+}
+impl SlhDsaPk {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+impl Pk for SlhDsaPk {
+    type Sk = SlhDsaSk;
+    const ID: u8 = 6;
+
+    fn with(sk: &Self::Sk) -> Self {
+        let mut data = [0u8; SLHDSA_PK_LEN];
+        data.copy_from_slice(&sk.0.verifying_key().to_bytes());
+        Self(data)
+    }
+
+    fn verify(&self, message: impl Into<SigHash>, sig: &<Self::Sk as Sk>::Sig) -> bool {
+        let (Ok(pk), Ok(sig)) = (
+            VerifyingKey::<Sha2_128s>::try_from(&self.0[..]),
+            Signature::<Sha2_128s>::try_from(&sig.0[..]),
+        ) else {
+            return false;
+        };
+        pk.verify(message.into().as_slice(), &sig).is_ok()
+    }
+
+    fn to_mnemonic(&self) -> String { self.to_baid58().mnemonic() }
+}
+
+impl StrictType for SlhDsaPk {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for SlhDsaPk {}
+impl StrictTuple for SlhDsaPk {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for SlhDsaPk {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for SlhDsaPk {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data: [u8; SLHDSA_PK_LEN] = r.read_field()?;
+            VerifyingKey::<Sha2_128s>::try_from(&data[..])
+                .map_err(|err| DecodeError::DataIntegrityError(err.to_string()))?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for SlhDsaPk {
+    fn strict_dumb() -> Self { Self([0xFAu8; SLHDSA_PK_LEN]) }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SlhDsaSig([u8; SLHDSA_SIG_LEN]);
+
+impl Sig for SlhDsaSig {}
+
+impl StrictType for SlhDsaSig {
+    const STRICT_LIB_NAME: &'static str = LIB_NAME_SSID;
+}
+impl StrictProduct for SlhDsaSig {}
+impl StrictTuple for SlhDsaSig {
+    const FIELD_COUNT: u8 = 1;
+}
+impl StrictEncode for SlhDsaSig {
+    fn strict_encode<W: TypedWrite>(&self, writer: W) -> io::Result<W> {
+        writer.write_newtype::<Self>(&self.0)
+    }
+}
+impl StrictDecode for SlhDsaSig {
+    fn strict_decode(reader: &mut impl TypedRead) -> Result<Self, DecodeError> {
+        reader.read_tuple(|r| {
+            let data = r.read_field()?;
+            Ok(Self(data))
+        })
+    }
+}
+impl StrictDumb for SlhDsaSig {
+    fn strict_dumb() -> Self { Self([0xFAu8; SLHDSA_SIG_LEN]) }
+}
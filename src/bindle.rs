@@ -32,13 +32,14 @@ use std::str::FromStr;
 use std::{fs, io};
 
 use amplify::confinement;
-use amplify::confinement::{Confined, TinyVec, U24};
+use amplify::confinement::{TinyVec, U24};
 use baid58::Baid58ParseError;
 use strict_encoding::{
     DecodeError, StrictDecode, StrictDeserialize, StrictDumb, StrictEncode, StrictReader,
     StrictSerialize, StrictType, StrictWriter, STRICT_TYPES_LIB,
 };
 
+use crate::secret::{strict_deserialize_secret, SecretBuf};
 use crate::{Pk, RistrettoPk, SigCert};
 
 pub trait BindleContent: StrictSerialize + StrictDeserialize + StrictDumb {
@@ -59,6 +60,11 @@ pub trait BindleContent: StrictSerialize + StrictDeserialize + StrictDumb {
 
     fn bindle_id(&self) -> Self::Id;
     fn bindle_headers(&self) -> BTreeMap<&'static str, String> { none!() }
+    /// Restores the in-memory data reported by [`Self::bindle_headers`], which
+    /// is not a part of the serialized content, from the parsed headers.
+    fn restore_bindle_headers(&mut self, _headers: &BTreeMap<&str, &str>) -> Result<(), String> {
+        Ok(())
+    }
     fn bindle(self) -> Bindle<Self> { Bindle::new(self) }
     fn bindle_mnemonic(&self) -> Option<String> { None }
 }
@@ -95,6 +101,11 @@ impl<C: BindleContent, K: Pk> Bindle<C, K> {
     }
 
     pub fn id(&self) -> C::Id { self.id }
+    pub fn sigs(&self) -> &[SigCert<K>] { &self.sigs }
+
+    pub fn add_sig(&mut self, sig: SigCert<K>) -> Result<(), confinement::Error> {
+        self.sigs.push(sig)
+    }
 
     pub fn into_split(self) -> (C, TinyVec<SigCert<K>>) { (self.data, self.sigs) }
     pub fn unbindle(self) -> C { self.data }
@@ -110,6 +121,9 @@ pub enum BindleParseError<Id: Copy + Eq + Debug + Display> {
     /// Id header of the bindle contains unparsable information. Details: {0}
     InvalidId(Baid58ParseError),
 
+    /// bindle header contains invalid information. Details: {0}
+    InvalidHeader(String),
+
     /// the actual data doesn't match the provided id.
     ///
     /// Actual id: {actual}.
@@ -141,17 +155,24 @@ impl<C: BindleContent, K: Pk> FromStr for Bindle<C, K> {
             return Err(BindleParseError::WrongStructure);
         }
         let mut header_id = None;
+        let mut headers = BTreeMap::new();
         for line in lines.by_ref() {
             if line.is_empty() {
                 break;
             }
             if let Some(id_str) = line.strip_prefix("Id: ") {
                 header_id = Some(C::Id::from_str(id_str).map_err(BindleParseError::InvalidId)?);
+            } else if let Some((name, value)) = line.split_once(": ") {
+                headers.insert(name, value);
             }
         }
-        let armor = lines.filter(|l| !l.is_empty()).collect::<String>();
-        let data = base85::decode(&armor)?;
-        let data = C::from_strict_serialized::<U24>(Confined::try_from(data)?)?;
+        let armor = SecretBuf::new(lines.filter(|l| !l.is_empty()).collect::<String>());
+        let data = SecretBuf::new(base85::decode(&armor)?);
+        if data.len() > U24 {
+            return Err(BindleParseError::TooLarge);
+        }
+        let mut data = strict_deserialize_secret::<C>(&data)?;
+        data.restore_bindle_headers(&headers).map_err(BindleParseError::InvalidHeader)?;
         let id = data.bindle_id();
         if let Some(header_id) = header_id {
             if header_id != id {
@@ -187,8 +208,9 @@ impl<C: BindleContent, K: Pk> Display for Bindle<C, K> {
         writeln!(f)?;
 
         // TODO: Replace with streamed writer
-        let data = self.data.to_strict_serialized::<U24>().expect("in-memory");
-        let data = base85::encode(&data);
+        let data =
+            SecretBuf::new(self.data.to_strict_serialized::<U24>().expect("in-memory").release());
+        let data = SecretBuf::new(base85::encode(&data));
         let mut data = data.as_str();
         while data.len() >= 64 {
             let (line, rest) = data.split_at(64);
@@ -0,0 +1,131 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Human-friendly comparison of identities.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::sighash::tagged_hash;
+use crate::{Identity, Pk};
+
+const SAFETY_NUMBER_TAG: &str = "SSID/safety-number";
+
+/// Number of five-digit groups in a [`SafetyNumber`].
+pub const SAFETY_NUMBER_GROUPS: usize = 6;
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum KeyMnemonicParseError {
+    /// key mnemonic contains no words.
+    Empty,
+
+    /// invalid mnemonic word '{0}'.
+    InvalidWord(String),
+}
+
+/// Key mnemonic words parsed from a user input, as produced by
+/// [`Pk::to_mnemonic`].
+///
+/// The words are a checksum and not an encoding of the key, so they can't be
+/// converted back into a key; instead they are matched against known keys.
+///
+/// The checksum is only 32 bits long, so, just like the short
+/// [`crate::Fingerprint`], a key matching given words can be found by brute
+/// force. A match must be confirmed with the [`crate::LongFingerprint`] or a
+/// [`SafetyNumber`] before the key is trusted.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct KeyMnemonic(Vec<String>);
+
+impl FromStr for KeyMnemonic {
+    type Err = KeyMnemonicParseError;
+
+    /// Parses words separated with dashes, underscores or whitespaces, in any
+    /// letter case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s
+            .split(|c: char| c == '-' || c == '_' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                if word.chars().all(|c| c.is_ascii_alphabetic()) {
+                    Ok(word.to_ascii_lowercase())
+                } else {
+                    Err(KeyMnemonicParseError::InvalidWord(word.to_owned()))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if words.is_empty() {
+            return Err(KeyMnemonicParseError::Empty);
+        }
+        Ok(KeyMnemonic(words))
+    }
+}
+
+impl Display for KeyMnemonic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result { f.write_str(&self.0.join("-")) }
+}
+
+impl KeyMnemonic {
+    pub fn words(&self) -> &[String] { &self.0 }
+
+    /// Checks whether the words match the mnemonic of the key.
+    pub fn matches<K: Pk>(&self, key: &K) -> bool {
+        KeyMnemonic::from_str(&key.to_mnemonic()).is_ok_and(|words| words == *self)
+    }
+
+    /// Returns all of the `keys` matching the words.
+    pub fn find<'k, K: Pk + 'k>(&self, keys: impl IntoIterator<Item = &'k K>) -> Vec<&'k K> {
+        keys.into_iter().filter(|key| self.matches(*key)).collect()
+    }
+}
+
+/// Number computed from two identities, which their owners may compare in
+/// person or over an authenticated channel to make sure they hold each other's
+/// genuine identity.
+///
+/// The number doesn't depend on the order of the identities, so both parties
+/// get the same value.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct SafetyNumber([u32; SAFETY_NUMBER_GROUPS]);
+
+impl SafetyNumber {
+    pub fn with<K: Pk>(identity: &Identity<K>, other: &Identity<K>) -> Self {
+        let mut ids = [identity, other].map(Identity::to_sig_data);
+        ids.sort();
+        let hash = tagged_hash(SAFETY_NUMBER_TAG, [ids[0].as_slice(), ids[1].as_slice()].concat());
+        let mut groups = [0u32; SAFETY_NUMBER_GROUPS];
+        for (group, chunk) in groups.iter_mut().zip(hash.as_slice().chunks_exact(5)) {
+            let mut data = [0u8; 8];
+            data[..5].copy_from_slice(chunk);
+            *group = (u64::from_le_bytes(data) % 100_000) as u32;
+        }
+        SafetyNumber(groups)
+    }
+
+    pub fn groups(&self) -> [u32; SAFETY_NUMBER_GROUPS] { self.0 }
+}
+
+impl Display for SafetyNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let groups = self.0.map(|group| format!("{group:05}"));
+        f.write_str(&groups.join(" "))
+    }
+}
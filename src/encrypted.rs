@@ -0,0 +1,281 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Passphrase-encrypted secret key containers.
+//!
+//! The encryption key is derived from the passphrase with Argon2id and the
+//! strict-encoded secret key is encrypted with XChaCha20-Poly1305, using the
+//! strict-encoded public key as the associated data.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use amplify::confinement::{Confined, SmallBlob, U24};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::{thread_rng, Rng};
+use strict_encoding::{DeserializeError, StrictDeserialize, StrictSerialize};
+use zeroize::Zeroizing;
+
+use crate::secret::{strict_deserialize_secret, SecretBuf};
+use crate::{Bindle, BindleContent, BindleParseError, LoadError, Pk, RistrettoPk, LIB_NAME_SSID};
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum UnlockError {
+    /// the secret key is encrypted, but no passphrase was provided.
+    PassphraseRequired,
+
+    /// invalid passphrase or corrupted encrypted secret key.
+    Decryption,
+
+    /// invalid key derivation parameters. Details: {0}
+    Kdf(String),
+
+    /// key derivation parameters m={0}, t={1}, p={2} exceed the supported
+    /// limits.
+    KdfLimits(u32, u32, u32),
+
+    /// decrypted secret key can't be deserialized. Details: {0}
+    #[from]
+    Deserialize(DeserializeError),
+
+    /// decrypted secret key doesn't match the public key of the container.
+    KeyMismatch,
+}
+
+/// Maximal memory size of the Argon2id key derivation in KiB (256 MiB).
+pub const KDF_MAX_M_COST: u32 = 256 * 1024;
+/// Maximal number of the Argon2id key derivation iterations.
+pub const KDF_MAX_T_COST: u32 = 16;
+/// Maximal degree of parallelism of the Argon2id key derivation.
+pub const KDF_MAX_P_COST: u32 = 16;
+
+/// Argon2id parameters used to derive the encryption key from a passphrase.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct KdfParams {
+    pub salt: [u8; 16],
+    /// Memory size in KiB.
+    pub m_cost: u32,
+    /// Number of iterations.
+    pub t_cost: u32,
+    /// Degree of parallelism.
+    pub p_cost: u32,
+}
+
+impl KdfParams {
+    /// Constructs parameters with a random salt and the default Argon2id costs.
+    pub fn new() -> Self {
+        let mut salt = [0u8; 16];
+        thread_rng().fill(&mut salt);
+        KdfParams {
+            salt,
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+
+    /// Derives the encryption key, refusing costs above [`KDF_MAX_M_COST`],
+    /// [`KDF_MAX_T_COST`] and [`KDF_MAX_P_COST`], which may come from an
+    /// untrusted file.
+    fn derive_key(&self, passphrase: &str) -> Result<Zeroizing<[u8; 32]>, UnlockError> {
+        if self.m_cost > KDF_MAX_M_COST
+            || self.t_cost > KDF_MAX_T_COST
+            || self.p_cost > KDF_MAX_P_COST
+        {
+            return Err(UnlockError::KdfLimits(self.m_cost, self.t_cost, self.p_cost));
+        }
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(32))
+            .map_err(|err| UnlockError::Kdf(err.to_string()))?;
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), &self.salt, &mut *key)
+            .map_err(|err| UnlockError::Kdf(err.to_string()))?;
+        Ok(key)
+    }
+}
+
+impl Default for KdfParams {
+    fn default() -> Self { Self::new() }
+}
+
+/// Secret key encrypted with a passphrase.
+///
+/// The public key is kept in plaintext, such that the container can be
+/// identified without the passphrase.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct EncryptedSk<K: Pk = RistrettoPk> {
+    pk: K,
+    kdf: KdfParams,
+    nonce: [u8; 24],
+    ciphertext: SmallBlob,
+}
+
+impl<K: Pk> StrictSerialize for EncryptedSk<K> {}
+impl<K: Pk> StrictDeserialize for EncryptedSk<K> {}
+
+impl<K: Pk> BindleContent for EncryptedSk<K> {
+    const MAGIC: [u8; 4] = *b"SSEK";
+    const PLATE_TITLE: &'static str = "SSID ENCRYPTED SECRET KEY";
+    type Id = K;
+
+    fn bindle_id(&self) -> Self::Id { self.pk }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        bmap! {
+            "Encryption" => s!("XChaCha20-Poly1305"),
+            "Kdf" => format!(
+                "Argon2id m={} t={} p={}",
+                self.kdf.m_cost, self.kdf.t_cost, self.kdf.p_cost
+            )
+        }
+    }
+}
+
+impl<K: Pk> EncryptedSk<K> {
+    /// Encrypts secret key with a passphrase.
+    pub fn encrypt(sk: &K::Sk, passphrase: &str) -> Self {
+        Self::encrypt_with(sk, passphrase, KdfParams::new())
+            .expect("default key derivation parameters")
+    }
+
+    /// Encrypts secret key with a passphrase using custom key derivation
+    /// parameters.
+    ///
+    /// Fails if the parameters are not accepted by Argon2id or exceed the
+    /// limits applied on decryption.
+    pub fn encrypt_with(sk: &K::Sk, passphrase: &str, kdf: KdfParams) -> Result<Self, UnlockError> {
+        let pk = K::with(sk);
+        let key = kdf.derive_key(passphrase)?;
+        let mut nonce = [0u8; 24];
+        thread_rng().fill(&mut nonce);
+        let data = SecretBuf::new(sk.to_strict_serialized::<U24>().expect("in-memory").release());
+        let ciphertext = XChaCha20Poly1305::new(key.as_slice().into())
+            .encrypt(XNonce::from_slice(&nonce), Payload {
+                msg: &data,
+                aad: &pk.to_key_data(),
+            })
+            .expect("in-memory encryption");
+        Ok(EncryptedSk {
+            pk,
+            kdf,
+            nonce,
+            ciphertext: Confined::try_from(ciphertext).expect("secret key too large"),
+        })
+    }
+
+    /// Public key corresponding to the encrypted secret key.
+    pub fn pk(&self) -> K { self.pk }
+
+    /// Decrypts the secret key with the passphrase.
+    pub fn unlock(&self, passphrase: &str) -> Result<K::Sk, UnlockError> {
+        let key = self.kdf.derive_key(passphrase)?;
+        let data = XChaCha20Poly1305::new(key.as_slice().into())
+            .decrypt(XNonce::from_slice(&self.nonce), Payload {
+                msg: self.ciphertext.as_slice(),
+                aad: &self.pk.to_key_data(),
+            })
+            .map(SecretBuf::new)
+            .map_err(|_| UnlockError::Decryption)?;
+        let sk = strict_deserialize_secret::<K::Sk>(&data)?;
+        if K::with(&sk) != self.pk {
+            return Err(UnlockError::KeyMismatch);
+        }
+        Ok(sk)
+    }
+
+    /// Changes the passphrase, re-encrypting the secret key with a fresh salt
+    /// and nonce.
+    pub fn rekey(&self, passphrase: &str, new_passphrase: &str) -> Result<Self, UnlockError> {
+        let sk = self.unlock(passphrase)?;
+        let kdf = KdfParams {
+            salt: KdfParams::new().salt,
+            ..self.kdf
+        };
+        Self::encrypt_with(&sk, new_passphrase, kdf)
+    }
+}
+
+/// Secret key stored either in plaintext or passphrase-encrypted bindle.
+pub enum StoredSk<K: Pk = RistrettoPk> {
+    Plain(K::Sk),
+    Encrypted(EncryptedSk<K>),
+}
+
+impl<K: Pk> StoredSk<K>
+where K::Sk: BindleContent<Id = K>
+{
+    /// Loads secret key from a binary bindle file of either plaintext or
+    /// encrypted secret key.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let mut magic = [0u8; 4];
+        fs::File::open(path.as_ref())?.read_exact(&mut magic)?;
+        if magic == K::Sk::MAGIC {
+            Bindle::<K::Sk>::load(path).map(|bindle| Self::Plain(bindle.unbindle()))
+        } else if magic == EncryptedSk::<K>::MAGIC {
+            Bindle::<EncryptedSk<K>>::load(path).map(|bindle| Self::Encrypted(bindle.unbindle()))
+        } else {
+            Err(LoadError::InvalidMagic)
+        }
+    }
+
+    pub fn is_encrypted(&self) -> bool { matches!(self, StoredSk::Encrypted(_)) }
+
+    pub fn pk(&self) -> K {
+        match self {
+            StoredSk::Plain(sk) => K::with(sk),
+            StoredSk::Encrypted(enc) => enc.pk(),
+        }
+    }
+
+    /// Returns the secret key, decrypting it with the passphrase if needed.
+    pub fn unlock(self, passphrase: Option<&str>) -> Result<K::Sk, UnlockError> {
+        match self {
+            StoredSk::Plain(sk) => Ok(sk),
+            StoredSk::Encrypted(enc) => {
+                enc.unlock(passphrase.ok_or(UnlockError::PassphraseRequired)?)
+            }
+        }
+    }
+}
+
+impl<K: Pk> FromStr for StoredSk<K>
+where K::Sk: BindleContent<Id = K>
+{
+    type Err = BindleParseError<K>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let encrypted = format!("-----BEGIN {}-----", EncryptedSk::<K>::PLATE_TITLE);
+        if s.lines().next() == Some(&encrypted) {
+            Bindle::<EncryptedSk<K>>::from_str(s).map(|bindle| Self::Encrypted(bindle.unbindle()))
+        } else {
+            Bindle::<K::Sk>::from_str(s).map(|bindle| Self::Plain(bindle.unbindle()))
+        }
+    }
+}
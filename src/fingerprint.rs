@@ -0,0 +1,121 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Collision-resistant fingerprints of public keys.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::hex::{self, FromHex, ToHex};
+use amplify::Bytes32;
+
+use crate::sighash::tagged_hash;
+use crate::{Fingerprint, Pk, LIB_NAME_SSID};
+
+/// Current version of the [`LongFingerprint`] scheme.
+pub const LONG_FINGERPRINT_VERSION: u8 = 1;
+
+const LONG_FINGERPRINT_TAG: &str = "SSID/fingerprint";
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum LongFingerprintParseError {
+    /// invalid fingerprint hex encoding - {0}.
+    #[from]
+    Hex(hex::Error),
+
+    /// fingerprint must be {expected} bytes long, while {found} bytes are
+    /// provided.
+    InvalidLength { expected: usize, found: usize },
+
+    /// unsupported fingerprint version {0}.
+    UnknownVersion(u8),
+}
+
+/// Full-length fingerprint of a public key, computed as a tagged SHA256 hash of
+/// the key algorithm id and the key data, and prefixed with the version of
+/// the fingerprinting scheme.
+///
+/// Unlike the short [`crate::Fingerprint`], which is only an alias for
+/// displaying a key to humans, this fingerprint is collision-resistant and
+/// must be used whenever identities are matched by a program.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct LongFingerprint {
+    version: u8,
+    hash: Bytes32,
+}
+
+impl LongFingerprint {
+    /// Computes fingerprint of a key, which algorithm is identified by `algo`
+    /// (normally its [`Pk::ID`]).
+    pub(crate) fn with_key<K: Pk>(algo: u8, key: &K) -> Self {
+        let data = key.to_key_data();
+        LongFingerprint {
+            version: LONG_FINGERPRINT_VERSION,
+            hash: tagged_hash(LONG_FINGERPRINT_TAG, [&[algo][..], data.as_slice()].concat()),
+        }
+    }
+
+    pub fn version(&self) -> u8 { self.version }
+
+    pub fn hash(&self) -> Bytes32 { self.hash }
+
+    /// Short fingerprint, which is the prefix of the hash.
+    pub fn to_short(&self) -> Fingerprint {
+        Fingerprint::copy_from_slice(&self.hash[..4]).expect("fixed length")
+    }
+
+    pub fn to_byte_array(&self) -> [u8; 33] {
+        let mut data = [0u8; 33];
+        data[0] = self.version;
+        data[1..].copy_from_slice(self.hash.as_slice());
+        data
+    }
+}
+
+impl Display for LongFingerprint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_byte_array().to_hex())
+    }
+}
+
+impl FromStr for LongFingerprint {
+    type Err = LongFingerprintParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = Vec::<u8>::from_hex(s)?;
+        if data.len() != 33 {
+            return Err(LongFingerprintParseError::InvalidLength {
+                expected: 33,
+                found: data.len(),
+            });
+        }
+        if data[0] != LONG_FINGERPRINT_VERSION {
+            return Err(LongFingerprintParseError::UnknownVersion(data[0]));
+        }
+        Ok(LongFingerprint {
+            version: data[0],
+            hash: Bytes32::copy_from_slice(&data[1..]).expect("fixed length"),
+        })
+    }
+}
@@ -0,0 +1,720 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! FROST threshold Schnorr signatures over Ed25519 (RFC 9591).
+//!
+//! A group of `total` participants jointly controls a single [`RistrettoPk`],
+//! such that any `threshold` of them can produce an ordinary Ed25519
+//! [`RistrettoSig`] verifiable with [`Pk::verify`], while no participant ever
+//! holds the full secret key. Key shares are produced either by a trusted
+//! dealer ([`FrostKeyShare::trusted_dealer`]) or by the distributed key generation
+//! ([`DkgParticipant`]).
+//!
+//! Signing takes two rounds: each signer publishes [`SigningCommitments`] with
+//! [`FrostKeyShare::commit`], and after receiving the commitments of all
+//! signers and the message produces a [`SignatureShare`] with
+//! [`FrostKeyShare::sign`]. The shares are combined by any party into the final
+//! signature with [`FrostGroup::aggregate`].
+//!
+//! [`Pk::verify`]: crate::Pk::verify
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use amplify::confinement::{Confined, SmallOrdMap, SmallVec};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha512};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{RistrettoPk, RistrettoSig, SigHash, LIB_NAME_SSID};
+
+/// Context string of the FROST(Ed25519, SHA-512) ciphersuite.
+const CONTEXT: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// Compressed Edwards point.
+pub type FrostPoint = [u8; 32];
+/// Canonically encoded scalar.
+pub type FrostScalar = [u8; 32];
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum FrostError {
+    /// invalid threshold {threshold} for {total} participants; the threshold
+    /// must be at least 2 and must not exceed the number of participants.
+    InvalidThreshold { threshold: u16, total: u16 },
+
+    /// participant {0} is not a member of the group.
+    InvalidParticipant(u16),
+
+    /// participant {0} is present more than once.
+    DuplicateParticipant(u16),
+
+    /// data from participant {0} are missing.
+    MissingParticipant(u16),
+
+    /// data from participant {0} contain invalid point or scalar encoding.
+    InvalidEncoding(u16),
+
+    /// participant {0} provided invalid number of polynomial commitments.
+    InvalidCommitments(u16),
+
+    /// proof of knowledge of the secret provided by participant {0} is invalid.
+    InvalidProof(u16),
+
+    /// secret share from participant {0} doesn't match its commitments.
+    InvalidShare(u16),
+
+    /// {present} signers take part in the signing, while at least {threshold}
+    /// are required.
+    NotEnoughSigners { present: usize, threshold: u16 },
+
+    /// signing nonces don't match commitments of participant {0}.
+    NonceMismatch(u16),
+
+    /// signature share from participant {0} is invalid.
+    InvalidSignatureShare(u16),
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    let mut engine = Sha512::new();
+    for part in parts {
+        engine.update(part);
+    }
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&engine.finalize());
+    output
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar { Scalar::from_bytes_mod_order_wide(&hash(parts)) }
+
+fn random_scalar() -> Scalar {
+    let mut bytes = Zeroizing::new([0u8; 64]);
+    thread_rng().fill(&mut bytes[..]);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+fn identifier(id: u16) -> Scalar { Scalar::from(id as u64) }
+
+/// Decodes point, rejecting the identity and points having a small-order
+/// component.
+fn decode_point(point: &FrostPoint, id: u16) -> Result<EdwardsPoint, FrostError> {
+    CompressedEdwardsY(*point)
+        .decompress()
+        .filter(|point| point.is_torsion_free() && !point.is_identity())
+        .ok_or(FrostError::InvalidEncoding(id))
+}
+
+fn decode_scalar(scalar: &FrostScalar, id: u16) -> Result<Scalar, FrostError> {
+    Option::from(Scalar::from_canonical_bytes(*scalar)).ok_or(FrostError::InvalidEncoding(id))
+}
+
+fn check_params(threshold: u16, total: u16) -> Result<(), FrostError> {
+    if threshold < 2 || threshold > total {
+        return Err(FrostError::InvalidThreshold { threshold, total });
+    }
+    Ok(())
+}
+
+/// Evaluates secret polynomial at point `x`.
+fn evaluate(coefficients: &[Scalar], x: u16) -> Scalar {
+    let x = identifier(x);
+    coefficients.iter().rev().fold(Scalar::ZERO, |acc, coeff| acc * x + coeff)
+}
+
+/// Evaluates commitment to a secret polynomial at point `x`.
+fn evaluate_commitments(commitments: &[EdwardsPoint], x: u16) -> EdwardsPoint {
+    let x = identifier(x);
+    let mut acc = commitments[commitments.len() - 1];
+    for commitment in commitments.iter().rev().skip(1) {
+        acc = acc * x + commitment;
+    }
+    acc
+}
+
+/// Lagrange coefficient of participant `id` for the interpolation at zero.
+fn lagrange(id: u16, participants: impl Iterator<Item = u16>) -> Scalar {
+    let x = identifier(id);
+    let (num, den) = participants
+        .filter(|other| *other != id)
+        .map(identifier)
+        .fold((Scalar::ONE, Scalar::ONE), |(num, den), other| (num * other, den * (other - x)));
+    num * den.invert()
+}
+
+/// Public information about a FROST group, required to verify and aggregate
+/// signature shares.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct FrostGroup {
+    /// Minimal number of signers.
+    pub threshold: u16,
+    /// Group public key.
+    pub pk: RistrettoPk,
+    /// Public keys corresponding to the key shares of each of the participants.
+    pub verifying_shares: SmallOrdMap<u16, FrostPoint>,
+}
+
+/// Secret key share of a FROST group participant.
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct FrostKeyShare {
+    id: u16,
+    secret: FrostScalar,
+    group: FrostGroup,
+}
+
+impl Drop for FrostKeyShare {
+    fn drop(&mut self) { self.secret.zeroize() }
+}
+
+/// Round one package of the distributed key generation, which is broadcasted
+/// to all other participants.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct DkgCommitment {
+    pub id: u16,
+    /// Commitments to the coefficients of the participant secret polynomial.
+    pub commitments: SmallVec<FrostPoint>,
+    /// Nonce commitment of the proof of knowledge of the secret.
+    pub proof_r: FrostPoint,
+    /// Response of the proof of knowledge of the secret.
+    pub proof_z: FrostScalar,
+}
+
+/// Round two package of the distributed key generation, which contains a
+/// secret share and must be sent to its recipient over a confidential channel.
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct DkgShare {
+    pub from: u16,
+    pub to: u16,
+    share: FrostScalar,
+}
+
+impl Drop for DkgShare {
+    fn drop(&mut self) { self.share.zeroize() }
+}
+
+/// State of a participant of the distributed key generation.
+pub struct DkgParticipant {
+    id: u16,
+    threshold: u16,
+    total: u16,
+    coefficients: Zeroizing<Vec<Scalar>>,
+}
+
+fn dkg_challenge(id: u16, secret_commitment: &EdwardsPoint, r: &EdwardsPoint) -> Scalar {
+    hash_to_scalar(&[
+        CONTEXT,
+        b"dkg",
+        &identifier(id).to_bytes(),
+        &secret_commitment.compress().to_bytes(),
+        &r.compress().to_bytes(),
+    ])
+}
+
+impl DkgParticipant {
+    /// Starts the distributed key generation, returning the participant state
+    /// and the round one package to broadcast.
+    ///
+    /// Participant ids must be in range `1..=total`.
+    pub fn new(id: u16, threshold: u16, total: u16) -> Result<(Self, DkgCommitment), FrostError> {
+        check_params(threshold, total)?;
+        if id == 0 || id > total {
+            return Err(FrostError::InvalidParticipant(id));
+        }
+        let coefficients =
+            Zeroizing::new((0..threshold).map(|_| random_scalar()).collect::<Vec<_>>());
+        let commitments =
+            coefficients.iter().map(|coeff| EdwardsPoint::mul_base(coeff).compress().to_bytes());
+        let k = Zeroizing::new(random_scalar());
+        let r = EdwardsPoint::mul_base(&k);
+        let c = dkg_challenge(id, &EdwardsPoint::mul_base(&coefficients[0]), &r);
+        let package = DkgCommitment {
+            id,
+            commitments: Confined::try_from_iter(commitments).expect("u16 threshold"),
+            proof_r: r.compress().to_bytes(),
+            proof_z: (*k + coefficients[0] * c).to_bytes(),
+        };
+        let me = DkgParticipant {
+            id,
+            threshold,
+            total,
+            coefficients,
+        };
+        Ok((me, package))
+    }
+
+    pub fn id(&self) -> u16 { self.id }
+
+    fn verify_commitments(
+        &self,
+        packages: &[DkgCommitment],
+    ) -> Result<BTreeMap<u16, Vec<EdwardsPoint>>, FrostError> {
+        let mut commitments = BTreeMap::new();
+        for package in packages {
+            if package.id == 0 || package.id > self.total {
+                return Err(FrostError::InvalidParticipant(package.id));
+            }
+            if package.commitments.len() != self.threshold as usize {
+                return Err(FrostError::InvalidCommitments(package.id));
+            }
+            let points = package
+                .commitments
+                .iter()
+                .map(|point| decode_point(point, package.id))
+                .collect::<Result<Vec<_>, _>>()?;
+            let r = decode_point(&package.proof_r, package.id)?;
+            let z = decode_scalar(&package.proof_z, package.id)?;
+            let c = dkg_challenge(package.id, &points[0], &r);
+            if EdwardsPoint::mul_base(&z) != r + points[0] * c {
+                return Err(FrostError::InvalidProof(package.id));
+            }
+            if commitments.insert(package.id, points).is_some() {
+                return Err(FrostError::DuplicateParticipant(package.id));
+            }
+        }
+        if let Some(id) = (1..=self.total).find(|id| !commitments.contains_key(id)) {
+            return Err(FrostError::MissingParticipant(id));
+        }
+        Ok(commitments)
+    }
+
+    /// Verifies round one packages of all participants (including own one) and
+    /// produces secret shares for each of the other participants.
+    pub fn round2(&self, packages: &[DkgCommitment]) -> Result<Vec<DkgShare>, FrostError> {
+        self.verify_commitments(packages)?;
+        Ok((1..=self.total)
+            .filter(|to| *to != self.id)
+            .map(|to| DkgShare {
+                from: self.id,
+                to,
+                share: evaluate(&self.coefficients, to).to_bytes(),
+            })
+            .collect())
+    }
+
+    /// Completes the distributed key generation using round one packages of
+    /// all participants and the secret shares sent to this participant.
+    pub fn finish(
+        self,
+        packages: &[DkgCommitment],
+        shares: &[DkgShare],
+    ) -> Result<FrostKeyShare, FrostError> {
+        let commitments = self.verify_commitments(packages)?;
+        let mut secret = Zeroizing::new(evaluate(&self.coefficients, self.id));
+        let mut received = BTreeSet::new();
+        for share in shares {
+            if share.to != self.id
+                || share.from == self.id
+                || !commitments.contains_key(&share.from)
+            {
+                return Err(FrostError::InvalidParticipant(share.from));
+            }
+            if !received.insert(share.from) {
+                return Err(FrostError::DuplicateParticipant(share.from));
+            }
+            let value = Zeroizing::new(decode_scalar(&share.share, share.from)?);
+            if EdwardsPoint::mul_base(&value)
+                != evaluate_commitments(&commitments[&share.from], self.id)
+            {
+                return Err(FrostError::InvalidShare(share.from));
+            }
+            *secret += *value;
+        }
+        if let Some(id) = (1..=self.total).find(|id| *id != self.id && !received.contains(id)) {
+            return Err(FrostError::MissingParticipant(id));
+        }
+
+        let pk: EdwardsPoint = commitments.values().map(|points| points[0]).sum();
+        let verifying_shares = (1..=self.total).map(|id| {
+            let point: EdwardsPoint =
+                commitments.values().map(|points| evaluate_commitments(points, id)).sum();
+            (id, point.compress().to_bytes())
+        });
+        let group = FrostGroup {
+            threshold: self.threshold,
+            pk: RistrettoPk::from_byte_array(pk.compress().to_bytes()),
+            verifying_shares: Confined::try_from_iter(verifying_shares).expect("u16 participants"),
+        };
+        Ok(FrostKeyShare {
+            id: self.id,
+            secret: secret.to_bytes(),
+            group,
+        })
+    }
+}
+
+/// Secret single-use nonces of a signer, produced in the first round of
+/// signing.
+pub struct SigningNonces {
+    hiding: Scalar,
+    binding: Scalar,
+}
+
+impl Drop for SigningNonces {
+    fn drop(&mut self) {
+        self.hiding.zeroize();
+        self.binding.zeroize();
+    }
+}
+
+/// Public commitments to the signer nonces, which are sent to all other
+/// signers in the first round of signing.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct SigningCommitments {
+    pub id: u16,
+    pub hiding: FrostPoint,
+    pub binding: FrostPoint,
+}
+
+/// Signature share produced by a signer in the second round of signing.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct SignatureShare {
+    pub id: u16,
+    pub z: FrostScalar,
+}
+
+/// Data of a signing session which are computed by each of the signers and by
+/// the aggregator.
+struct Session {
+    commitments: BTreeMap<u16, (EdwardsPoint, EdwardsPoint)>,
+    binding_factors: BTreeMap<u16, Scalar>,
+    group_commitment: EdwardsPoint,
+    challenge: Scalar,
+}
+
+impl Session {
+    fn lagrange(&self, id: u16) -> Scalar { lagrange(id, self.commitments.keys().copied()) }
+}
+
+impl FrostGroup {
+    fn session(
+        &self,
+        message: SigHash,
+        commitments: &[SigningCommitments],
+    ) -> Result<Session, FrostError> {
+        let mut sorted = BTreeMap::new();
+        for commitment in commitments {
+            let id = commitment.id;
+            if !self.verifying_shares.contains_key(&id) {
+                return Err(FrostError::InvalidParticipant(id));
+            }
+            let points =
+                (decode_point(&commitment.hiding, id)?, decode_point(&commitment.binding, id)?);
+            if sorted.insert(id, points).is_some() {
+                return Err(FrostError::DuplicateParticipant(id));
+            }
+        }
+        if sorted.len() < self.threshold as usize {
+            return Err(FrostError::NotEnoughSigners {
+                present: sorted.len(),
+                threshold: self.threshold,
+            });
+        }
+
+        let mut encoded = Vec::with_capacity(sorted.len() * 96);
+        for (id, (hiding, binding)) in &sorted {
+            encoded.extend_from_slice(&identifier(*id).to_bytes());
+            encoded.extend_from_slice(&hiding.compress().to_bytes());
+            encoded.extend_from_slice(&binding.compress().to_bytes());
+        }
+        let pk = self.pk.to_byte_array();
+        let msg_hash = hash(&[CONTEXT, b"msg", message.as_slice()]);
+        let com_hash = hash(&[CONTEXT, b"com", &encoded]);
+        let binding_factors = sorted
+            .keys()
+            .map(|id| {
+                let rho = hash_to_scalar(&[
+                    CONTEXT,
+                    b"rho",
+                    &pk,
+                    &msg_hash,
+                    &com_hash,
+                    &identifier(*id).to_bytes(),
+                ]);
+                (*id, rho)
+            })
+            .collect::<BTreeMap<_, _>>();
+        let group_commitment = sorted
+            .iter()
+            .map(|(id, (hiding, binding))| hiding + binding * binding_factors[id])
+            .sum::<EdwardsPoint>();
+        // Ed25519-compatible challenge, such that the aggregated signature is a
+        // valid Ed25519 signature
+        let challenge =
+            hash_to_scalar(&[&group_commitment.compress().to_bytes(), &pk, message.as_slice()]);
+
+        Ok(Session {
+            commitments: sorted,
+            binding_factors,
+            group_commitment,
+            challenge,
+        })
+    }
+
+    /// Verifies signature shares and aggregates them into a signature of the
+    /// group public key.
+    ///
+    /// If some of the shares are invalid, reports the first misbehaving
+    /// signer.
+    pub fn aggregate(
+        &self,
+        message: impl Into<SigHash>,
+        commitments: &[SigningCommitments],
+        shares: &[SignatureShare],
+    ) -> Result<RistrettoSig, FrostError> {
+        let session = self.session(message.into(), commitments)?;
+        let mut z = Scalar::ZERO;
+        let mut received = BTreeSet::new();
+        for share in shares {
+            let id = share.id;
+            let (hiding, binding) =
+                session.commitments.get(&id).ok_or(FrostError::InvalidParticipant(id))?;
+            if !received.insert(id) {
+                return Err(FrostError::DuplicateParticipant(id));
+            }
+            let z_i = decode_scalar(&share.z, id)?;
+            let verifying_share = decode_point(&self.verifying_shares[&id], id)?;
+            let expected = hiding
+                + binding * session.binding_factors[&id]
+                + verifying_share * (session.challenge * session.lagrange(id));
+            if EdwardsPoint::mul_base(&z_i) != expected {
+                return Err(FrostError::InvalidSignatureShare(id));
+            }
+            z += z_i;
+        }
+        if let Some(id) = session.commitments.keys().find(|id| !received.contains(id)) {
+            return Err(FrostError::MissingParticipant(*id));
+        }
+
+        let mut sig = [0u8; 64];
+        sig[..32].copy_from_slice(&session.group_commitment.compress().to_bytes());
+        sig[32..].copy_from_slice(&z.to_bytes());
+        Ok(RistrettoSig::from_byte_array(sig))
+    }
+}
+
+impl FrostKeyShare {
+    /// Generates key shares by a trusted dealer, which learns the group secret key
+    /// during the process and must securely erase it afterwards.
+    pub fn trusted_dealer(threshold: u16, total: u16) -> Result<Vec<Self>, FrostError> {
+        check_params(threshold, total)?;
+        let coefficients =
+            Zeroizing::new((0..threshold).map(|_| random_scalar()).collect::<Vec<_>>());
+        let secrets =
+            Zeroizing::new((1..=total).map(|id| evaluate(&coefficients, id)).collect::<Vec<_>>());
+        let verifying_shares = (1..=total)
+            .zip(secrets.iter())
+            .map(|(id, secret)| (id, EdwardsPoint::mul_base(secret).compress().to_bytes()));
+        let group = FrostGroup {
+            threshold,
+            pk: RistrettoPk::from_byte_array(
+                EdwardsPoint::mul_base(&coefficients[0]).compress().to_bytes(),
+            ),
+            verifying_shares: Confined::try_from_iter(verifying_shares).expect("u16 participants"),
+        };
+        Ok((1..=total)
+            .zip(secrets.iter())
+            .map(|(id, secret)| Self {
+                id,
+                secret: secret.to_bytes(),
+                group: group.clone(),
+            })
+            .collect())
+    }
+
+    pub fn id(&self) -> u16 { self.id }
+    pub fn group(&self) -> &FrostGroup { &self.group }
+    pub fn pk(&self) -> RistrettoPk { self.group.pk }
+
+    fn nonce(&self) -> Scalar {
+        let mut random = Zeroizing::new([0u8; 32]);
+        thread_rng().fill(&mut random[..]);
+        hash_to_scalar(&[CONTEXT, b"nonce", &random[..], &self.secret])
+    }
+
+    /// Produces single-use nonces and commitments to them for the first round
+    /// of signing.
+    pub fn commit(&self) -> (SigningNonces, SigningCommitments) {
+        let nonces = SigningNonces {
+            hiding: self.nonce(),
+            binding: self.nonce(),
+        };
+        let commitments = SigningCommitments {
+            id: self.id,
+            hiding: EdwardsPoint::mul_base(&nonces.hiding).compress().to_bytes(),
+            binding: EdwardsPoint::mul_base(&nonces.binding).compress().to_bytes(),
+        };
+        (nonces, commitments)
+    }
+
+    /// Produces signature share in the second round of signing, consuming the
+    /// nonces from the first round.
+    pub fn sign(
+        &self,
+        nonces: SigningNonces,
+        message: impl Into<SigHash>,
+        commitments: &[SigningCommitments],
+    ) -> Result<SignatureShare, FrostError> {
+        let session = self.group.session(message.into(), commitments)?;
+        let (hiding, binding) =
+            session.commitments.get(&self.id).ok_or(FrostError::MissingParticipant(self.id))?;
+        if (*hiding, *binding)
+            != (EdwardsPoint::mul_base(&nonces.hiding), EdwardsPoint::mul_base(&nonces.binding))
+        {
+            return Err(FrostError::NonceMismatch(self.id));
+        }
+        let secret = Zeroizing::new(decode_scalar(&self.secret, self.id)?);
+        let z = nonces.hiding
+            + nonces.binding * session.binding_factors[&self.id]
+            + session.lagrange(self.id) * *secret * session.challenge;
+        Ok(SignatureShare {
+            id: self.id,
+            z: z.to_bytes(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::constants::EIGHT_TORSION;
+
+    use super::*;
+    use crate::{Pk, SigDomain};
+
+    fn sign(shares: &[&FrostKeyShare], message: SigHash) -> Result<RistrettoSig, FrostError> {
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            shares.iter().map(|share| share.commit()).unzip();
+        let sig_shares = shares
+            .iter()
+            .zip(nonces)
+            .map(|(share, nonces)| share.sign(nonces, message, &commitments))
+            .collect::<Result<Vec<_>, _>>()?;
+        shares[0].group().aggregate(message, &commitments, &sig_shares)
+    }
+
+    #[test]
+    fn trusted_dealer_signing() {
+        let shares = FrostKeyShare::trusted_dealer(2, 3).unwrap();
+        let pk = shares[0].pk();
+        let message = SigHash::with(SigDomain::Message, b"FROST");
+        for signers in [[0, 1], [1, 2], [0, 2]] {
+            let sig = sign(&[&shares[signers[0]], &shares[signers[1]]], message).unwrap();
+            assert!(pk.verify(message, &sig));
+        }
+        let sig = sign(&[&shares[0], &shares[1], &shares[2]], message).unwrap();
+        assert!(pk.verify(message, &sig));
+        assert_eq!(sign(&[&shares[0]], message).unwrap_err(), FrostError::NotEnoughSigners {
+            present: 1,
+            threshold: 2
+        });
+    }
+
+    #[test]
+    fn dkg_signing() {
+        let (participants, packages): (Vec<_>, Vec<_>) =
+            (1..=5).map(|id| DkgParticipant::new(id, 3, 5).unwrap()).unzip();
+        let mut shares = participants
+            .iter()
+            .flat_map(|participant| participant.round2(&packages).unwrap())
+            .collect::<Vec<_>>();
+        let key_shares = participants
+            .into_iter()
+            .map(|participant| {
+                let id = participant.id();
+                let (mine, rest) = shares.drain(..).partition::<Vec<_>, _>(|share| share.to == id);
+                shares = rest;
+                participant.finish(&packages, &mine).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let pk = key_shares[0].pk();
+        assert!(key_shares.iter().all(|share| share.group() == key_shares[0].group()));
+
+        let message = SigHash::with(SigDomain::Message, b"FROST DKG");
+        let sig = sign(&[&key_shares[4], &key_shares[0], &key_shares[2]], message).unwrap();
+        assert!(pk.verify(message, &sig));
+    }
+
+    #[test]
+    fn invalid_share() {
+        let shares = FrostKeyShare::trusted_dealer(2, 3).unwrap();
+        let message = SigHash::with(SigDomain::Message, b"FROST");
+        let (nonces0, commitment0) = shares[0].commit();
+        let (nonces1, commitment1) = shares[1].commit();
+        let commitments = [commitment0, commitment1];
+        let share0 = shares[0].sign(nonces0, message, &commitments).unwrap();
+        let mut share1 = shares[1].sign(nonces1, message, &commitments).unwrap();
+        share1.z = share0.z;
+        assert_eq!(
+            shares[2].group().aggregate(message, &commitments, &[share0, share1]).unwrap_err(),
+            FrostError::InvalidSignatureShare(2)
+        );
+    }
+
+    /// Identity, small-order and mixed-order points.
+    fn weak_points() -> [FrostPoint; 3] {
+        [
+            EdwardsPoint::default().compress().to_bytes(),
+            EIGHT_TORSION[1].compress().to_bytes(),
+            (EIGHT_TORSION[1] + EdwardsPoint::mul_base(&random_scalar())).compress().to_bytes(),
+        ]
+    }
+
+    #[test]
+    fn weak_dkg_commitments() {
+        let (participants, packages): (Vec<_>, Vec<_>) =
+            (1..=3).map(|id| DkgParticipant::new(id, 2, 3).unwrap()).unzip();
+        for point in weak_points() {
+            let mut packages = packages.clone();
+            let mut commitments = packages[1].commitments.to_vec();
+            commitments[1] = point;
+            packages[1].commitments = Confined::try_from(commitments).unwrap();
+            assert_eq!(
+                participants[0].round2(&packages).unwrap_err(),
+                FrostError::InvalidEncoding(2)
+            );
+        }
+    }
+
+    #[test]
+    fn weak_nonce_commitments() {
+        let shares = FrostKeyShare::trusted_dealer(2, 3).unwrap();
+        let message = SigHash::with(SigDomain::Message, b"FROST");
+        for point in weak_points() {
+            let (nonces, commitment0) = shares[0].commit();
+            let (_, mut commitment1) = shares[1].commit();
+            commitment1.binding = point;
+            assert_eq!(
+                shares[0].sign(nonces, message, &[commitment0, commitment1]).unwrap_err(),
+                FrostError::InvalidEncoding(2)
+            );
+        }
+    }
+}
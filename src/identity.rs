@@ -19,32 +19,85 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use amplify::confinement::{SmallVec, U8};
-use commit_verify::{Digest as _, Sha256};
+use amplify::confinement::{SmallVec, U16};
+use bpstd::ScriptPubkey;
 use strict_encoding::{StrictDeserialize, StrictSerialize};
 
-use crate::{BindleContent, Digest, Fingerprint, Pk, Proof, RistrettoPk, Seal, Sk, LIB_NAME_SSID};
+use crate::{
+    AgreementError, BatchVerifyError, Bindle, BindleContent, Fingerprint, KeyAgreement,
+    LongFingerprint, MemSigner, Pk, Proof, RistrettoPk, Seal, SharedSecret, SigDomain, SigHash,
+    Signer, Sk, LIB_NAME_SSID,
+};
 
-pub struct Ssi<K: Pk = RistrettoPk> {
-    pub sk: K::Sk,
+/// Errors detected during verification of an identity certificate. Revocations
+/// are referenced by their zero-based index in the certificate.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum IdCertError {
+    /// genesis identity is not signed by its own key.
+    GenesisSig,
+
+    /// revocation #{0} provides proof from a different blockchain than the one
+    /// of the revoked seal.
+    ProofMismatch(usize),
+
+    /// seal transaction of revocation #{0} doesn't define the revoked seal.
+    SealTxMismatch(usize),
+
+    /// witness transaction of revocation #{0} doesn't spend the revoked seal.
+    SealNotClosed(usize),
+
+    /// witness transaction of revocation #{0} doesn't commit to the new
+    /// identity.
+    UncommittedRevocation(usize),
+
+    /// revocation #{0} introduces a key which was already used by the identity.
+    KeyReused(usize),
+
+    /// revocation #{0} introduces a seal which was already used by the identity.
+    SealReused(usize),
+
+    /// bindle id doesn't match the key of the current identity.
+    MismatchedId,
+
+    /// signature #{0} attached to the bindle is invalid.
+    BindleSig(usize),
+}
+
+/// Identity certificate together with the signer holding the identity key,
+/// which defaults to the secret key kept in memory.
+pub struct Ssi<K: Pk = RistrettoPk, S: Signer<Pk = K> = MemSigner<K>> {
+    pub signer: S,
     pub cert: IdCert<K>,
 }
 
 impl<K: Pk> Ssi<K> {
-    pub fn new(seal: Seal) -> Self {
-        let sk = K::Sk::generate();
-        let identity = Identity {
-            key: K::with(&sk),
-            seal,
-        };
-        let sig = sk.sign(identity);
-        Ssi {
-            sk,
-            cert: IdCert::new(identity, sig),
-        }
+    pub fn new(seal: Seal) -> Self { Self::with(K::Sk::generate(), seal) }
+
+    pub fn with(sk: K::Sk, seal: Seal) -> Self {
+        Self::with_signer(MemSigner::new(sk), seal).unwrap_or_else(|err| match err {})
+    }
+
+    pub fn sk(&self) -> &K::Sk { self.signer.sk() }
+}
+
+impl<K: Pk, S: Signer<Pk = K>> Ssi<K, S> {
+    /// Creates genesis identity controlled by the signer.
+    pub fn with_signer(signer: S, seal: Seal) -> Result<Self, S::Error> {
+        let cert = IdCert::with_signer(seal, &signer)?;
+        Ok(Ssi { signer, cert })
     }
 
     pub fn fingerprint(&self) -> Fingerprint { self.cert.fingerprint() }
+
+    pub fn long_fingerprint(&self) -> LongFingerprint { self.cert.long_fingerprint() }
+}
+
+impl<K: KeyAgreement> Ssi<K> {
+    /// Computes the secret shared with the owner of the `remote` identity.
+    pub fn agree(&self, remote: &Identity<K>) -> Result<SharedSecret, AgreementError> {
+        remote.key.agree(self.sk())
+    }
 }
 
 /// Has binary form included into the blockchain (witness in case of bitcoin)
@@ -61,16 +114,35 @@ impl<K: Pk> StrictSerialize for Identity<K> {}
 
 impl<K: Pk> Identity<K> {
     pub fn fingerprint(&self) -> Fingerprint { self.key.fingerprint() }
+
+    pub fn long_fingerprint(&self) -> LongFingerprint { self.key.long_fingerprint() }
+
+    /// Tagged hash of the revocation of this identity in favour of the
+    /// `new_identity`.
+    pub fn revocation_commitment(&self, new_identity: Identity<K>) -> SigHash {
+        SigHash::with(
+            SigDomain::Revocation,
+            [self.to_sig_data(), new_identity.to_sig_data()].concat(),
+        )
+    }
+
+    /// Data committed to by the hashes of the identity: the key data followed
+    /// by the serialized seal.
+    ///
+    /// For keys of a known type this matches the strict serialization of the
+    /// identity, while for [`crate::AnyPk`] keys the enum tag is skipped, such
+    /// that the hashes don't change when the certificate is converted between
+    /// the two.
+    pub(crate) fn to_sig_data(&self) -> Vec<u8> {
+        let seal =
+            self.seal.to_strict_serialized::<U16>().expect("serialized seal does not fit 64 kB");
+        [self.key.to_key_data(), seal.release()].concat()
+    }
 }
 
-impl<K: Pk> From<Identity<K>> for Digest {
+impl<K: Pk> From<Identity<K>> for SigHash {
     fn from(identity: Identity<K>) -> Self {
-        let data = identity
-            .to_strict_serialized::<U8>()
-            .expect("serialized identity does not fit 256 bytes");
-        let mut hasher = Sha256::new();
-        hasher.update(data);
-        <[u8; 32]>::from(hasher.finalize()).into()
+        SigHash::with(SigDomain::Identity, identity.to_sig_data())
     }
 }
 
@@ -108,6 +180,71 @@ impl<K: Pk> IdCert<K> {
     }
 
     pub fn fingerprint(&self) -> Fingerprint { self.identity().fingerprint() }
+
+    pub fn long_fingerprint(&self) -> LongFingerprint { self.identity().long_fingerprint() }
+
+    /// Verifies the genesis signature and every link of the revocation chain,
+    /// reporting the first link which fails.
+    pub fn verify(&self) -> Result<(), IdCertError> {
+        let genesis = self.genesis_id;
+        if !genesis.key.verify(genesis, &self.genesis_sig) {
+            return Err(IdCertError::GenesisSig);
+        }
+        self.verify_revocations()
+    }
+
+    /// Verifies every link of the revocation chain, but not the genesis
+    /// signature, which may be checked with [`IdCert::verify_sigs_batch`].
+    ///
+    /// Each revocation must spend the revoked seal with a witness transaction
+    /// having an `OP_RETURN` output with [`Identity::revocation_commitment`],
+    /// which binds the new identity to the spending. The inclusion of the
+    /// witness transaction into the blockchain is not verified.
+    pub fn verify_revocations(&self) -> Result<(), IdCertError> {
+        let mut prev = self.genesis_id;
+        let mut known = vec![prev];
+        for (no, revocation) in self.revocations.iter().enumerate() {
+            let (outpoint, proof) = match (prev.seal, &revocation.revocation_proof) {
+                (Seal::Bitcoin(outpoint), Proof::Bitcoin(proof))
+                | (Seal::Liquid(outpoint), Proof::Liquid(proof)) => (outpoint, proof),
+                _ => return Err(IdCertError::ProofMismatch(no)),
+            };
+            if proof.seal_tx.txid() != outpoint.txid {
+                return Err(IdCertError::SealTxMismatch(no));
+            }
+            if !proof.witness_tx.inputs.iter().any(|input| input.prev_output == outpoint) {
+                return Err(IdCertError::SealNotClosed(no));
+            }
+            let new = revocation.new_identity;
+            let commitment = ScriptPubkey::op_return(prev.revocation_commitment(new).as_slice());
+            if !proof.witness_tx.outputs.iter().any(|output| output.script_pubkey == commitment) {
+                return Err(IdCertError::UncommittedRevocation(no));
+            }
+            if known.iter().any(|id| id.key == new.key) {
+                return Err(IdCertError::KeyReused(no));
+            }
+            if known.iter().any(|id| id.seal == new.seal) {
+                return Err(IdCertError::SealReused(no));
+            }
+            known.push(new);
+            prev = new;
+        }
+        Ok(())
+    }
+
+    /// Checks genesis signatures of many certificates at once, reporting indexes
+    /// of the certificates having invalid signatures.
+    ///
+    /// Only the signatures are checked; this is intended to speed up loading of
+    /// large address books, which should check the revocations with
+    /// [`IdCert::verify_revocations`].
+    pub fn verify_sigs_batch(certs: &[Self]) -> Result<(), BatchVerifyError> {
+        let batch = certs
+            .iter()
+            .map(|cert| (cert.genesis_id.key, SigHash::from(cert.genesis_id), cert.genesis_sig))
+            .collect::<Vec<_>>();
+        K::verify_batch(&batch)
+    }
 }
 
 impl<K: Pk> BindleContent for IdCert<K> {
@@ -117,3 +254,18 @@ impl<K: Pk> BindleContent for IdCert<K> {
 
     fn bindle_id(&self) -> Self::Id { self.identity().key }
 }
+
+impl<K: Pk, S: Pk> Bindle<IdCert<K>, S> {
+    /// Verifies the certificate together with the bindle id and the signatures
+    /// attached to the bindle.
+    pub fn verify(&self) -> Result<(), IdCertError> {
+        if self.id() != self.bindle_id() {
+            return Err(IdCertError::MismatchedId);
+        }
+        IdCert::<K>::verify(self)?;
+        match self.sigs().iter().position(|sig| !sig.verify()) {
+            Some(no) => Err(IdCertError::BindleSig(no)),
+            None => Ok(()),
+        }
+    }
+}
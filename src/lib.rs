@@ -42,13 +42,65 @@ mod sigs;
 mod bindle;
 mod proofs;
 mod seal;
+mod sighash;
+mod fingerprint;
+mod compare;
+mod seed;
+mod secret;
+mod encrypted;
+mod shares;
+mod signer;
+#[cfg(feature = "frost")]
+mod frost;
+#[cfg(feature = "vrf")]
+mod vrf;
+#[cfg(feature = "ring")]
+mod ring;
+#[cfg(feature = "pkcs11")]
+mod pkcs11;
 
-pub use crate::algo::{Fingerprint, Pk, RistrettoPk, RistrettoSig, RistrettoSk, Sig, Sk};
+pub use crate::algo::{
+    AgreementError, AnyPk, AnySig, AnySk, BatchItem, BatchVerifyError, EcdsaPk, EcdsaSig, EcdsaSk,
+    Fingerprint, KeyAgreement, Pk, RistrettoPk, RistrettoSig, RistrettoSk, Secp256k1Pk,
+    Secp256k1Sig, Secp256k1Sk, SharedSecret, Sig, Sk,
+};
+#[cfg(feature = "bls")]
+pub use crate::algo::{BlsAggregateError, BlsAggregateSig, BlsPk, BlsSig, BlsSk};
+#[cfg(feature = "mldsa")]
+pub use crate::algo::{HybridPk, HybridSig, HybridSk, MlDsaPk, MlDsaSig, MlDsaSk};
+#[cfg(feature = "p256")]
+pub use crate::algo::{P256Pk, P256Sig, P256Sk};
+#[cfg(feature = "slhdsa")]
+pub use crate::algo::{SlhDsaPk, SlhDsaSig, SlhDsaSk};
 pub use crate::bindle::{Bindle, BindleContent, BindleParseError, LoadError};
-pub use crate::identity::{IdCert, Identity, Revocation, Ssi};
+pub use crate::compare::{KeyMnemonic, KeyMnemonicParseError, SafetyNumber, SAFETY_NUMBER_GROUPS};
+pub use crate::encrypted::{
+    EncryptedSk, KdfParams, StoredSk, UnlockError, KDF_MAX_M_COST, KDF_MAX_P_COST, KDF_MAX_T_COST,
+};
+pub use crate::fingerprint::{
+    LongFingerprint, LongFingerprintParseError, LONG_FINGERPRINT_VERSION,
+};
+#[cfg(feature = "frost")]
+pub use crate::frost::{
+    DkgCommitment, DkgParticipant, DkgShare, FrostError, FrostGroup, FrostKeyShare, FrostPoint,
+    FrostScalar, SignatureShare, SigningCommitments, SigningNonces,
+};
+pub use crate::identity::{IdCert, IdCertError, Identity, Revocation, Ssi};
+#[cfg(feature = "pkcs11")]
+pub use crate::pkcs11::{HsmError, HsmKeyRef, HsmSigner};
 pub use crate::proofs::{BpProof, Proof};
+#[cfg(feature = "ring")]
+pub use crate::ring::{KeyImage, RingError, RingSig, RingSigCert};
 pub use crate::seal::Seal;
+pub use crate::seed::{DerivationParseError, DerivationPath, HARDENED_INDEX_BOUNDARY};
+pub use crate::shares::{ShareError, SkShare};
+pub use crate::sighash::{SigDomain, SigHash};
+#[cfg(feature = "async")]
+pub use crate::signer::AsyncSigner;
+pub use crate::signer::{MemSigner, RecordingSigner, Signer, SignerSig};
 pub use crate::sigs::{SigCert, Signature};
+#[cfg(feature = "vrf")]
+pub use crate::vrf::{VrfCert, VrfError, VrfOutput, VrfProof};
 
 pub const LIB_NAME_SSID: &str = "SSID";
 
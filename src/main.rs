@@ -24,15 +24,36 @@ extern crate amplify;
 #[macro_use]
 extern crate clap;
 
-use std::path::PathBuf;
+#[cfg(feature = "pkcs11")]
+use std::fmt::{self, Display, Formatter};
+use std::fs::OpenOptions;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::{fs, io};
 
+use amplify::hex;
+use bip39::Mnemonic;
 use bpstd::Address;
 use clap::{Parser, ValueHint};
-use ssid::{BindleContent, Fingerprint, RistrettoPk, Seal, Ssi};
+use sha2::{Digest, Sha256};
+use ssid::{
+    Bindle, BindleContent, DerivationPath, EncryptedSk, Fingerprint, IdCert, KeyMnemonic,
+    LongFingerprint, LongFingerprintParseError, Pk, RistrettoPk, RistrettoSk, SafetyNumber, Seal,
+    SigCert, SkShare, Ssi, StoredSk,
+};
+#[cfg(feature = "pkcs11")]
+use ssid::{HsmKeyRef, HsmSigner};
+use zeroize::Zeroizing;
 
 pub const DATA_DIR_ENV: &str = "SSID_DATA_DIR";
+pub const PASSPHRASE_ENV: &str = "SSID_PASSPHRASE";
+pub const KEY_PASSWORD_ENV: &str = "SSID_KEY_PASSWORD";
+pub const NEW_KEY_PASSWORD_ENV: &str = "SSID_NEW_KEY_PASSWORD";
+pub const PKCS11_MODULE_ENV: &str = "SSID_PKCS11_MODULE";
+pub const PKCS11_PIN_ENV: &str = "SSID_PKCS11_PIN";
 #[cfg(any(target_os = "linux"))]
 pub const DATA_DIR: &str = "~/.ssid";
 #[cfg(any(target_os = "freebsd", target_os = "openbsd", target_os = "netbsd"))]
@@ -94,6 +115,129 @@ pub enum Command {
     Generate {
         /// Single-use-seal definition which should be used for revocation
         seal: Seal,
+
+        /// Derive the secret key from a newly generated BIP39 mnemonic, which
+        /// is printed out for the backup
+        #[arg(long)]
+        mnemonic: bool,
+
+        /// Optional BIP39 passphrase protecting the mnemonic
+        #[arg(long, requires = "mnemonic", env = PASSPHRASE_ENV, hide_env_values = true)]
+        passphrase: Option<String>,
+
+        /// SLIP-10 hardened derivation path of the identity key from the
+        /// mnemonic seed
+        #[arg(long, requires = "mnemonic", default_value = "m")]
+        derivation: DerivationPath,
+
+        /// Encrypt the secret key file with a password
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+
+        /// Generate the key inside a PKCS#11 token under the provided label;
+        /// the secret key never leaves the token
+        #[arg(long, conflicts_with_all = ["mnemonic", "password"])]
+        hsm: Option<String>,
+
+        /// PKCS#11 module library of the token
+        #[arg(long, requires = "hsm", env = PKCS11_MODULE_ENV, value_hint = ValueHint::FilePath)]
+        pkcs11_module: Option<PathBuf>,
+
+        /// PKCS#11 slot of the token; defaults to the first slot with a token
+        #[arg(long, requires = "hsm")]
+        pkcs11_slot: Option<u64>,
+
+        /// User PIN of the token, which is required for generating the key
+        #[arg(long, requires = "hsm", env = PKCS11_PIN_ENV, hide_env_values = true)]
+        pkcs11_pin: Option<String>,
+    },
+
+    /// Restore identity secret key from a BIP39 mnemonic read from the
+    /// standard input
+    Restore {
+        /// Single-use-seal definition which should be used for revocation
+        seal: Seal,
+
+        /// Optional BIP39 passphrase used during the generation
+        #[arg(long, env = PASSPHRASE_ENV, hide_env_values = true)]
+        passphrase: Option<String>,
+
+        /// SLIP-10 hardened derivation path of the identity key from the
+        /// mnemonic seed
+        #[arg(long, default_value = "m")]
+        derivation: DerivationPath,
+
+        /// Encrypt the secret key file with a password
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+    },
+
+    /// Change password of the secret key file, encrypting or decrypting it
+    Passwd {
+        /// Identity which secret key file should be updated
+        identity: IdArg,
+
+        /// Current password, if the secret key file is encrypted
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+
+        /// New password; if absent the secret key file is decrypted
+        #[arg(long, env = NEW_KEY_PASSWORD_ENV, hide_env_values = true)]
+        new_password: Option<String>,
+    },
+
+    /// Split secret key into shares, a threshold number of which is able to
+    /// recover the key
+    Split {
+        /// Identity which secret key should be split
+        identity: IdArg,
+
+        /// Number of shares required to recover the secret key
+        threshold: u8,
+
+        /// Total number of shares to produce
+        total: u8,
+
+        /// Password of the secret key file, if it is encrypted
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+    },
+
+    /// Recover secret key from its shares
+    Combine {
+        /// Files containing secret key shares
+        #[arg(required = true, value_hint = ValueHint::FilePath)]
+        shares: Vec<PathBuf>,
+
+        /// File containing the identity certificate of the recovered key;
+        /// required unless the certificate is already in the data directory
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        cert: Option<PathBuf>,
+
+        /// Encrypt the recovered secret key file with a password
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+    },
+
+    /// Print safety number of an own identity and some other identity, which
+    /// should be compared with the one computed by the other party
+    Compare {
+        /// Own identity
+        identity: IdArg,
+
+        /// File containing the other identity certificate
+        #[arg(value_hint = ValueHint::FilePath)]
+        other: PathBuf,
+    },
+
+    /// Find known identities matching the key mnemonic words
+    ///
+    /// The words are a 32-bit checksum of the key, which can be forged by brute
+    /// force; confirm the match with the full fingerprint.
+    Match {
+        /// Mnemonic words of the identity key
+        #[arg(required = true)]
+        words: Vec<String>,
     },
 
     /// Revoke existing key
@@ -120,8 +264,23 @@ pub enum Command {
     /// Export public information about identity
     Export {},
 
-    /// Sign using identity
-    Sign {},
+    /// Sign a file using identity, printing out the signature
+    Sign {
+        /// Identity which key should be used for signing
+        identity: IdArg,
+
+        /// File to sign
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+
+        /// Password of the secret key file, if it is encrypted
+        #[arg(long, env = KEY_PASSWORD_ENV, hide_env_values = true)]
+        password: Option<String>,
+
+        /// User PIN of the token, if the key is held by a hardware token
+        #[arg(long, env = PKCS11_PIN_ENV, hide_env_values = true)]
+        pkcs11_pin: Option<String>,
+    },
 
     /// Verify signature
     Verify {},
@@ -133,37 +292,550 @@ pub enum Command {
     Decrypt {},
 }
 
+/// Reference to an identity by either its short or full-length fingerprint.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Display, From)]
 #[display(inner)]
 pub enum IdArg {
     #[from]
     Fingerprint(Fingerprint),
+
+    #[from]
+    LongFingerprint(LongFingerprint),
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum IdArgParseError {
+    /// invalid short fingerprint - {0}.
+    Short(hex::Error),
+
+    /// {0}
+    #[from]
+    Long(LongFingerprintParseError),
 }
 
 impl FromStr for IdArg {
-    type Err = amplify::hex::Error;
+    type Err = IdArgParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Short fingerprints are 4 bytes long
+        if s.len() == 8 {
+            Fingerprint::from_str(s).map(Self::Fingerprint).map_err(IdArgParseError::Short)
+        } else {
+            LongFingerprint::from_str(s).map(Self::LongFingerprint).map_err(IdArgParseError::from)
+        }
+    }
+}
+
+/// Reads all public identity files in the data directory, returning the base
+/// names of the identity files together with the identity certificates.
+fn known_ids(data_dir: &Path) -> Result<Vec<(String, Bindle<IdCert>)>, io::Error> {
+    let mut ids = vec![];
+    for entry in fs::read_dir(data_dir)? {
+        let path = entry?.path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix("_pub"))
+        else {
+            continue;
+        };
+        let Some(cert) =
+            fs::read_to_string(&path).ok().and_then(|data| Bindle::<IdCert>::from_str(&data).ok())
+        else {
+            continue;
+        };
+        ids.push((name.to_owned(), cert));
+    }
+    Ok(ids)
+}
+
+/// Finds the identity among the public identity files in the data directory,
+/// returning the base name of the identity files.
+///
+/// Fails if a short fingerprint matches more than a single identity.
+fn resolve_id(data_dir: &Path, id: &IdArg) -> Result<String, io::Error> {
+    let mut matches = known_ids(data_dir)?
+        .into_iter()
+        .filter(|(_, cert)| match id {
+            IdArg::Fingerprint(fp) => cert.fingerprint() == *fp,
+            IdArg::LongFingerprint(fp) => cert.long_fingerprint() == *fp,
+        })
+        .map(|(name, cert)| (name, cert.long_fingerprint()))
+        .collect::<Vec<_>>();
+    match matches.len() {
+        0 => Err(io::Error::new(io::ErrorKind::NotFound, format!("unknown identity {id}"))),
+        1 => Ok(matches.remove(0).0),
+        _ => {
+            let list =
+                matches.iter().map(|(_, fp)| format!("  {fp}")).collect::<Vec<_>>().join("\n");
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "short fingerprint {id} is ambiguous; use one of the full fingerprints \
+                     instead:\n{list}"
+                ),
+            ))
+        }
+    }
+}
+
+fn load_cert(path: &Path) -> Result<IdCert, io::Error> {
+    Bindle::<IdCert>::from_str(&fs::read_to_string(path)?)
+        .map(Bindle::unbindle)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
+
+/// Writes the file making it accessible to its owner only, since it contains
+/// secret data.
+fn write_secret(path: impl AsRef<Path>, data: &[u8]) -> Result<(), io::Error> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(path)?;
+    // the mode is applied only to the newly created files
+    #[cfg(unix)]
+    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    file.write_all(data)
+}
+
+fn save_sk(sk_file: &Path, sk: RistrettoSk, password: Option<&str>) -> Result<(), io::Error> {
+    let data = match password {
+        Some(password) => EncryptedSk::<RistrettoPk>::encrypt(&sk, password).bindle().to_string(),
+        None => sk.bindle().to_string(),
+    };
+    // the file may hold the only copy of the key, so it is replaced only once
+    // the new data are fully written
+    let mut tmp_file = sk_file.as_os_str().to_owned();
+    tmp_file.push(".tmp");
+    write_secret(&tmp_file, Zeroizing::new(data).as_bytes())?;
+    fs::rename(tmp_file, sk_file)
+}
+
+fn load_sk(sk_file: &Path, password: Option<&str>) -> Result<RistrettoSk, io::Error> {
+    let data = Zeroizing::new(fs::read_to_string(sk_file)?);
+    StoredSk::<RistrettoPk>::from_str(&data)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?
+        .unlock(password)
+        .map_err(|err| io::Error::new(io::ErrorKind::PermissionDenied, err.to_string()))
+}
+
+/// Location of the identity key held by a PKCS#11 token, which is saved in the
+/// `_hsm` file of the identity.
+#[cfg(feature = "pkcs11")]
+#[derive(Clone, Eq, PartialEq, Debug)]
+struct HsmRecord {
+    module: PathBuf,
+    slot: Option<u64>,
+    label: String,
+}
+
+#[cfg(feature = "pkcs11")]
+impl Display for HsmRecord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Module: {}", self.module.display())?;
+        if let Some(slot) = self.slot {
+            writeln!(f, "Slot: {slot}")?;
+        }
+        writeln!(f, "Label: {}", self.label)
+    }
+}
+
+#[cfg(feature = "pkcs11")]
+impl FromStr for HsmRecord {
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Fingerprint::from_str(s).map(Self::Fingerprint)
+        let (mut module, mut slot, mut label) = (None, None, None);
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            match line.split_once(": ") {
+                Some(("Module", value)) => module = Some(PathBuf::from(value)),
+                Some(("Slot", value)) => {
+                    slot = Some(value.parse().map_err(|_| format!("invalid slot '{value}'"))?)
+                }
+                Some(("Label", value)) => label = Some(value.to_owned()),
+                _ => return Err(format!("invalid line '{line}'")),
+            }
+        }
+        Ok(HsmRecord {
+            module: module.ok_or("PKCS#11 module is not specified")?,
+            slot,
+            label: label.ok_or("key label is not specified")?,
+        })
     }
 }
 
+#[cfg(feature = "pkcs11")]
+impl HsmRecord {
+    fn key_ref<'a>(&'a self, pin: &'a str) -> HsmKeyRef<'a> {
+        HsmKeyRef {
+            module: &self.module,
+            slot: self.slot,
+            pin,
+            label: &self.label,
+        }
+    }
+}
+
+/// Returns the token PIN, failing if it is not provided, since logging in with
+/// an empty PIN is never intended.
+#[cfg(feature = "pkcs11")]
+fn require_pin(pin: Option<String>) -> Result<Zeroizing<String>, io::Error> {
+    pin.map(Zeroizing::new).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("PKCS#11 user PIN is not specified; use --pkcs11-pin or {PKCS11_PIN_ENV}"),
+        )
+    })
+}
+
+/// Reads location of the identity key held by a hardware token, if any.
+fn read_hsm_file(data_dir: &Path, name: &str) -> Result<Option<String>, io::Error> {
+    let mut hsm_file = data_dir.to_owned();
+    hsm_file.push(format!("{name}_hsm"));
+    match fs::read_to_string(hsm_file) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Fails if the secret key of the identity is held by a hardware token and
+/// can't be accessed.
+fn check_exportable(data_dir: &Path, name: &str) -> Result<(), io::Error> {
+    let mut hsm_file = data_dir.to_owned();
+    hsm_file.push(format!("{name}_hsm"));
+    if hsm_file.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "secret key of identity {name} is held by a hardware token and can't be exported"
+            ),
+        ));
+    }
+    Ok(())
+}
+
+fn save_ssi(data_dir: &Path, ssi: Ssi, password: Option<&str>) -> Result<(), io::Error> {
+    let fp = ssi.long_fingerprint();
+    let (mut sk_file, mut pk_file) = (data_dir.to_owned(), data_dir.to_owned());
+    sk_file.push(format!("{fp}"));
+    pk_file.push(format!("{fp}_pub"));
+    save_sk(&sk_file, ssi.signer.into_sk(), password)?;
+    fs::write(pk_file, &ssi.cert.bindle().to_string())?;
+    Ok(())
+}
+
 fn main() -> Result<(), io::Error> {
     let mut cli = Cli::parse();
     cli.process()?;
 
     match cli.command {
-        Command::Generate { seal } => {
+        #[cfg(feature = "pkcs11")]
+        Command::Generate {
+            seal,
+            hsm: Some(label),
+            pkcs11_module,
+            pkcs11_slot,
+            pkcs11_pin,
+            ..
+        } => {
+            let module = pkcs11_module.ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput, "PKCS#11 module is not specified")
+            })?;
+            let record = HsmRecord {
+                module,
+                slot: pkcs11_slot,
+                label,
+            };
+            let pin = require_pin(pkcs11_pin)?;
+            let signer = HsmSigner::generate(&record.key_ref(&pin))
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+            let cert = IdCert::with_signer(seal, &signer)
+                .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+            let fp = cert.long_fingerprint();
+            let (mut hsm_file, mut pk_file) = (cli.data_dir.clone(), cli.data_dir.clone());
+            hsm_file.push(format!("{fp}_hsm"));
+            pk_file.push(format!("{fp}_pub"));
+            fs::write(hsm_file, record.to_string())?;
+            fs::write(pk_file, cert.bindle().to_string())?;
+        }
+        #[cfg(not(feature = "pkcs11"))]
+        Command::Generate { hsm: Some(_), .. } => {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "the tool is compiled without PKCS#11 support",
+            ));
+        }
+        Command::Generate {
+            seal,
+            mnemonic: false,
+            password,
+            ..
+        } => {
             let ssi = Ssi::<RistrettoPk>::new(seal);
-            let fp = ssi.fingerprint();
+            save_ssi(&cli.data_dir, ssi, password.as_deref())?;
+        }
+        Command::Generate {
+            seal,
+            mnemonic: true,
+            passphrase,
+            derivation,
+            password,
+            ..
+        } => {
+            let (ssi, mnemonic) =
+                Ssi::with_mnemonic(seal, passphrase.as_deref().unwrap_or_default(), &derivation);
+            save_ssi(&cli.data_dir, ssi, password.as_deref())?;
+            println!("{mnemonic}");
+        }
+        Command::Restore {
+            seal,
+            passphrase,
+            derivation,
+            password,
+        } => {
+            let mut words = Zeroizing::new(String::new());
+            io::stdin().read_line(&mut words)?;
+            let mnemonic = Mnemonic::parse(words.trim())
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+            let passphrase = passphrase.as_deref().unwrap_or_default();
+            let ssi = Ssi::from_mnemonic(&mnemonic, passphrase, &derivation, seal);
+            save_ssi(&cli.data_dir, ssi, password.as_deref())?;
+        }
+        Command::Passwd {
+            identity,
+            password,
+            new_password,
+        } => {
+            let name = resolve_id(&cli.data_dir, &identity)?;
+            check_exportable(&cli.data_dir, &name)?;
+            let mut sk_file = cli.data_dir.clone();
+            sk_file.push(name);
+            let sk = load_sk(&sk_file, password.as_deref())?;
+            save_sk(&sk_file, sk, new_password.as_deref())?;
+        }
+        Command::Split {
+            identity,
+            threshold,
+            total,
+            password,
+        } => {
+            let name = resolve_id(&cli.data_dir, &identity)?;
+            check_exportable(&cli.data_dir, &name)?;
+            let mut sk_file = cli.data_dir.clone();
+            sk_file.push(&name);
+            let sk = load_sk(&sk_file, password.as_deref())?;
+            let shares = SkShare::<RistrettoPk>::split(&sk, threshold, total)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+            for share in shares {
+                let mut share_file = cli.data_dir.clone();
+                share_file.push(format!("{name}_share{}", share.index()));
+                write_secret(share_file, Zeroizing::new(share.bindle().to_string()).as_bytes())?;
+            }
+        }
+        Command::Combine {
+            shares,
+            cert,
+            password,
+        } => {
+            let shares = shares
+                .iter()
+                .map(|path| {
+                    let data = Zeroizing::new(fs::read_to_string(path)?);
+                    Bindle::<SkShare>::from_str(&data)
+                        .map(Bindle::unbindle)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let sk = SkShare::<RistrettoPk>::combine(&shares)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+            let fp = RistrettoPk::with(&sk).long_fingerprint();
             let (mut sk_file, mut pk_file) = (cli.data_dir.clone(), cli.data_dir.clone());
             sk_file.push(format!("{fp}"));
             pk_file.push(format!("{fp}_pub"));
-            fs::write(sk_file, &ssi.sk.bindle().to_string())?;
-            fs::write(pk_file, &ssi.cert.bindle().to_string())?;
+            // without the identity certificate the key can't be found by the
+            // other commands
+            match cert {
+                Some(path) => {
+                    let cert = load_cert(&path)?;
+                    if cert.long_fingerprint() != fp {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!(
+                                "certificate {} doesn't match the recovered key {fp}",
+                                path.display()
+                            ),
+                        ));
+                    }
+                    fs::write(pk_file, cert.bindle().to_string())?;
+                }
+                None if pk_file.exists() => {}
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!(
+                            "identity certificate of the recovered key {fp} is not known; provide \
+                             it with --cert"
+                        ),
+                    ));
+                }
+            }
+            save_sk(&sk_file, sk, password.as_deref())?;
+        }
+        Command::Compare { identity, other } => {
+            let mut pk_file = cli.data_dir.clone();
+            pk_file.push(format!("{}_pub", resolve_id(&cli.data_dir, &identity)?));
+            let own = load_cert(&pk_file)?.identity();
+            let other = load_cert(&other)?.identity();
+            println!("Own identity:    {}", own.key.to_mnemonic());
+            println!("Other identity:  {}", other.key.to_mnemonic());
+            println!("Safety number:   {}", SafetyNumber::with(&own, &other));
+        }
+        Command::Match { words } => {
+            let mnemonic = KeyMnemonic::from_str(&words.join(" "))
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+            for (_, cert) in known_ids(&cli.data_dir)? {
+                if mnemonic.matches(&cert.identity().key) {
+                    println!("{}\t{}", cert.long_fingerprint(), cert.identity());
+                }
+            }
+            eprintln!(
+                "Key mnemonics are 32-bit checksums which can be forged by brute force; confirm \
+                 the full fingerprint before trusting a match."
+            );
+        }
+        Command::Sign {
+            identity,
+            file,
+            password,
+            pkcs11_pin,
+        } => {
+            let name = resolve_id(&cli.data_dir, &identity)?;
+            let (mut sk_file, mut pk_file) = (cli.data_dir.clone(), cli.data_dir.clone());
+            sk_file.push(&name);
+            pk_file.push(format!("{name}_pub"));
+            let cert = load_cert(&pk_file)?;
+            let digest: [u8; 32] = Sha256::digest(fs::read(file)?).into();
+            let sig = match read_hsm_file(&cli.data_dir, &name)? {
+                #[cfg(feature = "pkcs11")]
+                Some(record) => {
+                    let record = HsmRecord::from_str(&record)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                    let pin = require_pin(pkcs11_pin)?;
+                    let signer = HsmSigner::open(&record.key_ref(&pin))
+                        .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+                    SigCert::with_signer(digest, &signer, cert)
+                        .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?
+                }
+                #[cfg(not(feature = "pkcs11"))]
+                Some(_) => {
+                    let _ = pkcs11_pin;
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "the tool is compiled without PKCS#11 support",
+                    ));
+                }
+                None => SigCert::new(digest, &load_sk(&sk_file, password.as_deref())?, cert),
+            };
+            println!("{}", sig.bindle());
         }
         _ => todo!(),
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEAL: &str = "bitcoin:1111111111111111111111111111111111111111111111111111111111111111:0";
+
+    /// Creates data directory with the public identity files of the given
+    /// names, where the same index stands for the same identity.
+    fn data_dir(test: &str, ids: &[usize]) -> (PathBuf, Vec<IdCert>) {
+        let dir = std::env::temp_dir().join(format!("ssid-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let seal = Seal::from_str(SEAL).unwrap();
+        let certs = (0..=*ids.iter().max().unwrap())
+            .map(|_| Ssi::<RistrettoPk>::new(seal).cert)
+            .collect::<Vec<_>>();
+        for (no, id) in ids.iter().enumerate() {
+            fs::write(dir.join(format!("id{no}_pub")), certs[*id].clone().bindle().to_string())
+                .unwrap();
+        }
+        (dir, certs)
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn secret_file_mode() {
+        let path = std::env::temp_dir().join(format!("ssid-secret-{}", std::process::id()));
+        fs::write(&path, b"public").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        write_secret(&path, b"secret").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read(&path).unwrap(), b"secret");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn id_arg() {
+        let fp = Ssi::<RistrettoPk>::new(Seal::from_str(SEAL).unwrap()).cert.long_fingerprint();
+        assert_eq!(IdArg::from_str(&fp.to_string()), Ok(IdArg::LongFingerprint(fp)));
+        assert_eq!(
+            IdArg::from_str(&fp.to_short().to_string()),
+            Ok(IdArg::Fingerprint(fp.to_short()))
+        );
+        assert!(matches!(IdArg::from_str("0123456z"), Err(IdArgParseError::Short(_))));
+    }
+
+    #[test]
+    fn resolve_unique() {
+        let (dir, certs) = data_dir("resolve-unique", &[0, 1]);
+        for (no, cert) in certs.iter().enumerate() {
+            let name = format!("id{no}");
+            assert_eq!(resolve_id(&dir, &cert.fingerprint().into()).unwrap(), name);
+            assert_eq!(resolve_id(&dir, &cert.long_fingerprint().into()).unwrap(), name);
+        }
+        let unknown = Ssi::<RistrettoPk>::new(Seal::from_str(SEAL).unwrap()).cert;
+        let err = resolve_id(&dir, &unknown.fingerprint().into()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn resolve_ambiguous() {
+        // two identity files sharing the short fingerprint
+        let (dir, certs) = data_dir("resolve-ambiguous", &[0, 0, 1]);
+        let err = resolve_id(&dir, &certs[0].fingerprint().into()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains(&certs[0].long_fingerprint().to_string()));
+        assert_eq!(resolve_id(&dir, &certs[1].fingerprint().into()).unwrap(), "id2");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    #[cfg(feature = "pkcs11")]
+    fn hsm_record() {
+        let record = HsmRecord {
+            module: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+            slot: Some(3),
+            label: s!("ssid key"),
+        };
+        assert_eq!(HsmRecord::from_str(&record.to_string()), Ok(record.clone()));
+        let record = HsmRecord {
+            slot: None,
+            ..record
+        };
+        assert_eq!(HsmRecord::from_str(&record.to_string()), Ok(record));
+
+        // files written before the location was recorded contain just the label
+        assert!(HsmRecord::from_str("ssid key\n").is_err());
+        assert!(HsmRecord::from_str("Label: ssid key\n").is_err());
+        assert!(HsmRecord::from_str("Module: /lib.so\nSlot: x\nLabel: k\n").is_err());
+    }
+}
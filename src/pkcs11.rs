@@ -0,0 +1,228 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! PKCS#11 signer keeping Ed25519 identity keys inside a hardware security
+//! module or a smartcard.
+//!
+//! Keys are generated in the token as non-extractable objects and sign with
+//! `CKM_EDDSA` mechanism, producing ordinary [`RistrettoSig`] signatures, such
+//! that identities controlled by an HSM are indistinguishable from the ones
+//! with in-memory keys.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::error::RvError;
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::types::AuthPin;
+
+use crate::{Pk, RistrettoPk, RistrettoSig, SigHash, Signer};
+
+/// DER-encoded object identifier of Ed25519 curve (1.3.101.112).
+const ED25519_OID: [u8; 5] = [0x06, 0x03, 0x2B, 0x65, 0x70];
+
+#[derive(Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum HsmError {
+    /// PKCS#11 error - {0}.
+    #[from]
+    Pkcs11(cryptoki::error::Error),
+
+    /// no token is present in slot {0}.
+    NoSlot(u64),
+
+    /// no token is present.
+    NoToken,
+
+    /// token has no key labeled '{0}'.
+    KeyNotFound(String),
+
+    /// token already has a key labeled '{0}'.
+    KeyExists(String),
+
+    /// token returned invalid Ed25519 public key.
+    InvalidPk,
+
+    /// token produced invalid Ed25519 signature.
+    InvalidSig,
+}
+
+/// Returns initialized context of the PKCS#11 module.
+///
+/// A module may be initialized only once per process, so the contexts are kept
+/// until the process exits.
+fn context(module: &Path) -> Result<Pkcs11, HsmError> {
+    static CONTEXTS: OnceLock<Mutex<BTreeMap<PathBuf, Pkcs11>>> = OnceLock::new();
+    let mut contexts = CONTEXTS
+        .get_or_init(|| Mutex::new(BTreeMap::new()))
+        .lock()
+        .expect("poisoned PKCS#11 contexts");
+    if let Some(pkcs11) = contexts.get(module) {
+        return Ok(pkcs11.clone());
+    }
+    let pkcs11 = Pkcs11::new(module)?;
+    pkcs11.initialize(CInitializeArgs::OsThreads)?;
+    contexts.insert(module.to_owned(), pkcs11.clone());
+    Ok(pkcs11)
+}
+
+/// Location of a key inside a PKCS#11 token.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct HsmKeyRef<'a> {
+    /// Path to the PKCS#11 module library.
+    pub module: &'a Path,
+    /// Slot id; if absent the first slot with a token is used.
+    pub slot: Option<u64>,
+    /// User PIN of the token.
+    pub pin: &'a str,
+    /// Label of the key objects.
+    pub label: &'a str,
+}
+
+impl<'a> HsmKeyRef<'a> {
+    fn login(&self) -> Result<Session, HsmError> {
+        let pkcs11 = context(self.module)?;
+        let slots = pkcs11.get_slots_with_token()?;
+        let slot = match self.slot {
+            Some(id) => {
+                slots.into_iter().find(|slot| slot.id() == id).ok_or(HsmError::NoSlot(id))?
+            }
+            None => slots.into_iter().next().ok_or(HsmError::NoToken)?,
+        };
+        let session = pkcs11.open_rw_session(slot)?;
+        // The login state is shared by all sessions of the application
+        match session.login(UserType::User, Some(&AuthPin::new(self.pin.to_owned()))) {
+            Err(cryptoki::error::Error::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => {}
+            res => res?,
+        }
+        Ok(session)
+    }
+
+    fn find(
+        &self,
+        session: &Session,
+        class: ObjectClass,
+    ) -> Result<Option<ObjectHandle>, HsmError> {
+        let template = [
+            Attribute::Class(class),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Label(self.label.as_bytes().to_vec()),
+        ];
+        Ok(session.find_objects(&template)?.into_iter().next())
+    }
+}
+
+fn read_pk(session: &Session, handle: ObjectHandle) -> Result<RistrettoPk, HsmError> {
+    let point = session
+        .get_attributes(handle, &[AttributeType::EcPoint])?
+        .into_iter()
+        .find_map(|attr| match attr {
+            Attribute::EcPoint(point) => Some(point),
+            _ => None,
+        })
+        .ok_or(HsmError::InvalidPk)?;
+    // Tokens either return the raw key or wrap it into a DER octet string
+    let key = match point.as_slice() {
+        [0x04, 0x20, key @ ..] if key.len() == 32 => key,
+        key if key.len() == 32 => key,
+        _ => return Err(HsmError::InvalidPk),
+    };
+    let mut data = [0u8; 32];
+    data.copy_from_slice(key);
+    Ok(RistrettoPk::from_byte_array(data))
+}
+
+/// Signer using an Ed25519 key held by a PKCS#11 token.
+pub struct HsmSigner {
+    session: Mutex<Session>,
+    key: ObjectHandle,
+    pk: RistrettoPk,
+}
+
+impl HsmSigner {
+    /// Generates new non-extractable key in the token.
+    pub fn generate(key_ref: &HsmKeyRef) -> Result<Self, HsmError> {
+        let session = key_ref.login()?;
+        if key_ref.find(&session, ObjectClass::PRIVATE_KEY)?.is_some() {
+            return Err(HsmError::KeyExists(key_ref.label.to_owned()));
+        }
+        let label = key_ref.label.as_bytes().to_vec();
+        let public = [
+            Attribute::Token(true),
+            Attribute::Verify(true),
+            Attribute::EcParams(ED25519_OID.to_vec()),
+            Attribute::Label(label.clone()),
+        ];
+        let private = [
+            Attribute::Token(true),
+            Attribute::Private(true),
+            Attribute::Sensitive(true),
+            Attribute::Extractable(false),
+            Attribute::Sign(true),
+            Attribute::Label(label),
+        ];
+        let (pk, key) =
+            session.generate_key_pair(&Mechanism::EccEdwardsKeyPairGen, &public, &private)?;
+        let pk = read_pk(&session, pk)?;
+        Ok(HsmSigner {
+            session: Mutex::new(session),
+            key,
+            pk,
+        })
+    }
+
+    /// Opens existing key in the token.
+    pub fn open(key_ref: &HsmKeyRef) -> Result<Self, HsmError> {
+        let session = key_ref.login()?;
+        let not_found = || HsmError::KeyNotFound(key_ref.label.to_owned());
+        let key = key_ref.find(&session, ObjectClass::PRIVATE_KEY)?.ok_or_else(not_found)?;
+        let pk = key_ref.find(&session, ObjectClass::PUBLIC_KEY)?.ok_or_else(not_found)?;
+        let pk = read_pk(&session, pk)?;
+        Ok(HsmSigner {
+            session: Mutex::new(session),
+            key,
+            pk,
+        })
+    }
+}
+
+impl Signer for HsmSigner {
+    type Pk = RistrettoPk;
+    type Error = HsmError;
+
+    fn pk(&self) -> RistrettoPk { self.pk }
+
+    fn sign(&self, message: SigHash) -> Result<RistrettoSig, HsmError> {
+        let session = self.session.lock().expect("poisoned PKCS#11 session");
+        let data = session.sign(&Mechanism::Eddsa, self.key, message.as_slice())?;
+        let data = <[u8; 64]>::try_from(data.as_slice()).map_err(|_| HsmError::InvalidSig)?;
+        let sig = RistrettoSig::from_byte_array(data);
+        // Protects from faulty tokens and mismatching public key objects
+        if !self.pk.verify(message, &sig) {
+            return Err(HsmError::InvalidSig);
+        }
+        Ok(sig)
+    }
+}
@@ -0,0 +1,381 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Linkable spontaneous anonymous group (LSAG) signatures over Ed25519
+//! identity keys.
+//!
+//! A ring signature proves that the message is signed by the owner of one of
+//! the identities in the ring, without revealing which one. Each signature
+//! carries a [`KeyImage`], which is unique for a signer and a linking scope;
+//! two signatures with the same scope and key image are made by the same
+//! signer, which allows detecting double-signing (for instance, double
+//! voting).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use amplify::confinement::{self, Confined, SmallBlob, SmallVec};
+use amplify::Bytes32;
+use baid58::{Baid58ParseError, Chunking, FromBaid58, ToBaid58, CHUNKING_32};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::IsIdentity;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha512};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    BindleContent, IdCert, IdCertError, Identity, Pk, RistrettoPk, RistrettoSk, LIB_NAME_SSID,
+};
+
+const HASH_TO_POINT_TAG: &[u8] = b"SSID/ring/key-image";
+const CHALLENGE_TAG: &[u8] = b"SSID/ring/challenge";
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum RingError {
+    /// ring must contain at least two members.
+    RingTooSmall,
+
+    /// ring contains too many members.
+    #[from(confinement::Error)]
+    RingTooLarge,
+
+    /// ring member #{0} is present more than once.
+    DuplicateMember(usize),
+
+    /// ring member #{0} has key which can't be used for ring signatures.
+    InvalidKey(usize),
+
+    /// signer is not a member of the ring.
+    SignerNotInRing,
+
+    /// ring has {expected} members, while the signature is made for a ring of
+    /// {found} members.
+    RingSizeMismatch { expected: usize, found: usize },
+
+    /// certificate of ring member #{0} is invalid - {1}.
+    InvalidCert(usize, IdCertError),
+
+    /// invalid key image.
+    InvalidKeyImage,
+
+    /// invalid ring signature.
+    InvalidSignature,
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut engine = Sha512::new();
+    for part in parts {
+        engine.update(part);
+    }
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&engine.finalize());
+    Scalar::from_bytes_mod_order_wide(&output)
+}
+
+/// Maps the key to a curve point with unknown discrete logarithm using the
+/// try-and-increment method.
+fn hash_to_point(scope: &[u8], key: &CompressedEdwardsY) -> EdwardsPoint {
+    for ctr in 0..=u8::MAX {
+        let mut engine = Sha512::new();
+        engine.update(HASH_TO_POINT_TAG);
+        engine.update((scope.len() as u64).to_le_bytes());
+        engine.update(scope);
+        engine.update(key.as_bytes());
+        engine.update([ctr]);
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&engine.finalize()[..32]);
+        if let Some(point) = CompressedEdwardsY(candidate).decompress() {
+            let point = point.mul_by_cofactor();
+            if !point.is_identity() {
+                return point;
+            }
+        }
+    }
+    // Each iteration succeeds with probability of 1/2
+    unreachable!("no curve point found in 256 attempts")
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = Zeroizing::new([0u8; 64]);
+    thread_rng().fill(&mut bytes[..]);
+    Scalar::from_bytes_mod_order_wide(&bytes)
+}
+
+/// Ring members decoded into curve points, which are checked to be usable for
+/// the ring signatures.
+struct Ring {
+    keys: Vec<CompressedEdwardsY>,
+    points: Vec<EdwardsPoint>,
+    commitment: [u8; 64],
+}
+
+impl Ring {
+    fn with(ring: &[Identity<RistrettoPk>]) -> Result<Self, RingError> {
+        if ring.len() < 2 {
+            return Err(RingError::RingTooSmall);
+        }
+        let mut seen = BTreeSet::new();
+        let mut keys = Vec::with_capacity(ring.len());
+        let mut points = Vec::with_capacity(ring.len());
+        let mut engine = Sha512::new();
+        for (no, id) in ring.iter().enumerate() {
+            let key = CompressedEdwardsY(id.key.to_byte_array());
+            if !seen.insert(key.to_bytes()) {
+                return Err(RingError::DuplicateMember(no));
+            }
+            let point = key
+                .decompress()
+                .filter(|point| point.is_torsion_free() && !point.is_identity())
+                .ok_or(RingError::InvalidKey(no))?;
+            engine.update(key.as_bytes());
+            keys.push(key);
+            points.push(point);
+        }
+        let mut commitment = [0u8; 64];
+        commitment.copy_from_slice(&engine.finalize());
+        Ok(Ring {
+            keys,
+            points,
+            commitment,
+        })
+    }
+
+    fn challenge(
+        &self,
+        message: &[u8],
+        scope: &[u8],
+        image: &EdwardsPoint,
+        l: &EdwardsPoint,
+        r: &EdwardsPoint,
+    ) -> Scalar {
+        hash_to_scalar(&[
+            CHALLENGE_TAG,
+            &self.commitment,
+            &(message.len() as u64).to_le_bytes(),
+            message,
+            &(scope.len() as u64).to_le_bytes(),
+            scope,
+            image.compress().as_bytes(),
+            l.compress().as_bytes(),
+            r.compress().as_bytes(),
+        ])
+    }
+}
+
+/// Linkability tag of a ring signature, which is the same for all signatures
+/// made by the same key within the same scope.
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Display)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+#[display(Self::to_baid58_string)]
+pub struct KeyImage(Bytes32);
+
+impl ToBaid58<32> for KeyImage {
+    const HRI: &'static str = "ssr";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; 32] { self.0.to_byte_array() }
+    fn to_baid58_string(&self) -> String { self.to_string() }
+}
+impl FromBaid58<32> for KeyImage {}
+impl FromStr for KeyImage {
+    type Err = Baid58ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> { Self::from_baid58_chunked_str(s, ':', '#') }
+}
+impl From<[u8; 32]> for KeyImage {
+    fn from(value: [u8; 32]) -> Self { Self(value.into()) }
+}
+impl KeyImage {
+    pub fn to_baid58_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+
+    fn to_point(self) -> Result<EdwardsPoint, RingError> {
+        CompressedEdwardsY(self.0.to_byte_array())
+            .decompress()
+            .filter(|point| point.is_torsion_free() && !point.is_identity())
+            .ok_or(RingError::InvalidKeyImage)
+    }
+}
+
+/// LSAG signature over an ordered ring of identities.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct RingSig {
+    pub key_image: KeyImage,
+    pub challenge: Bytes32,
+    pub responses: SmallVec<Bytes32>,
+}
+
+impl StrictSerialize for RingSig {}
+impl StrictDeserialize for RingSig {}
+
+impl RingSig {
+    /// Signs the message as an anonymous member of the `ring`.
+    ///
+    /// The `scope` defines the context of linkability: signatures by the same
+    /// key have the same [`KeyImage`] only if they are made within the same
+    /// scope (for instance, a poll id).
+    pub fn sign(
+        message: impl AsRef<[u8]>,
+        scope: impl AsRef<[u8]>,
+        ring: &[Identity<RistrettoPk>],
+        sk: &RistrettoSk,
+    ) -> Result<Self, RingError> {
+        let (message, scope) = (message.as_ref(), scope.as_ref());
+        let ring = Ring::with(ring)?;
+        let pk = CompressedEdwardsY(RistrettoPk::with(sk).to_byte_array());
+        let signer =
+            ring.keys.iter().position(|key| *key == pk).ok_or(RingError::SignerNotInRing)?;
+        let n = ring.keys.len();
+
+        let (x, _) = sk.to_expanded();
+        let hp = hash_to_point(scope, &pk);
+        let image = hp * *x;
+
+        let alpha = Zeroizing::new(random_scalar());
+        let mut challenges = vec![Scalar::ZERO; n];
+        let mut responses = vec![Scalar::ZERO; n];
+        let mut c =
+            ring.challenge(message, scope, &image, &EdwardsPoint::mul_base(&alpha), &(hp * *alpha));
+        for offset in 1..n {
+            let i = (signer + offset) % n;
+            challenges[i] = c;
+            responses[i] = random_scalar();
+            let l = EdwardsPoint::mul_base(&responses[i]) + ring.points[i] * c;
+            let r = hash_to_point(scope, &ring.keys[i]) * responses[i] + image * c;
+            c = ring.challenge(message, scope, &image, &l, &r);
+        }
+        challenges[signer] = c;
+        responses[signer] = *alpha - c * *x;
+
+        Ok(RingSig {
+            key_image: image.compress().to_bytes().into(),
+            challenge: challenges[0].to_bytes().into(),
+            responses: Confined::try_from_iter(
+                responses.iter().map(|s| Bytes32::from(s.to_bytes())),
+            )?,
+        })
+    }
+
+    /// Checks that the message is signed by some member of the `ring`.
+    pub fn verify(
+        &self,
+        message: impl AsRef<[u8]>,
+        scope: impl AsRef<[u8]>,
+        ring: &[Identity<RistrettoPk>],
+    ) -> Result<(), RingError> {
+        let (message, scope) = (message.as_ref(), scope.as_ref());
+        let ring = Ring::with(ring)?;
+        if self.responses.len() != ring.keys.len() {
+            return Err(RingError::RingSizeMismatch {
+                expected: ring.keys.len(),
+                found: self.responses.len(),
+            });
+        }
+        let image = self.key_image.to_point()?;
+        let decode = |scalar: &Bytes32| {
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(scalar.to_byte_array()))
+                .ok_or(RingError::InvalidSignature)
+        };
+        let c0 = decode(&self.challenge)?;
+        let mut c = c0;
+        for (i, s) in self.responses.iter().enumerate() {
+            let s = decode(s)?;
+            let l = EdwardsPoint::mul_base(&s) + ring.points[i] * c;
+            let r = hash_to_point(scope, &ring.keys[i]) * s + image * c;
+            c = ring.challenge(message, scope, &image, &l, &r);
+        }
+        if c != c0 {
+            return Err(RingError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
+/// Ring signature over a message digest together with the certificates of the
+/// ring members, which may be verified without any other data.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct RingSigCert {
+    pub digest: Bytes32,
+    pub scope: SmallBlob,
+    pub ring: SmallVec<IdCert<RistrettoPk>>,
+    pub sig: RingSig,
+}
+
+impl StrictSerialize for RingSigCert {}
+impl StrictDeserialize for RingSigCert {}
+
+impl RingSigCert {
+    pub fn new(
+        digest: impl Into<Bytes32>,
+        scope: SmallBlob,
+        ring: SmallVec<IdCert<RistrettoPk>>,
+        sk: &RistrettoSk,
+    ) -> Result<Self, RingError> {
+        let digest = digest.into();
+        let identities = ring.iter().map(IdCert::identity).collect::<Vec<_>>();
+        let sig = RingSig::sign(digest.as_slice(), scope.as_slice(), &identities, sk)?;
+        Ok(RingSigCert {
+            digest,
+            scope,
+            ring,
+            sig,
+        })
+    }
+
+    pub fn key_image(&self) -> KeyImage { self.sig.key_image }
+
+    /// Checks the certificates of the ring members and that the digest is
+    /// signed by the current key of one of them.
+    pub fn verify(&self) -> Result<(), RingError> {
+        for (no, cert) in self.ring.iter().enumerate() {
+            cert.verify().map_err(|err| RingError::InvalidCert(no, err))?;
+        }
+        let identities = self.ring.iter().map(IdCert::identity).collect::<Vec<_>>();
+        self.sig.verify(self.digest.as_slice(), self.scope.as_slice(), &identities)
+    }
+
+    /// Detects whether both signatures are made by the same signer within the
+    /// same scope.
+    ///
+    /// The result is meaningful only for verified signatures.
+    pub fn is_linked(&self, other: &RingSigCert) -> bool {
+        self.scope == other.scope && self.key_image() == other.key_image()
+    }
+}
+
+impl BindleContent for RingSigCert {
+    const MAGIC: [u8; 4] = *b"SSRS";
+    const PLATE_TITLE: &'static str = "SSID RING SIGNATURE";
+    type Id = KeyImage;
+
+    fn bindle_id(&self) -> Self::Id { self.key_image() }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        bmap! {
+            "Digest" => self.digest.to_string(),
+            "Ring-Size" => self.ring.len().to_string()
+        }
+    }
+}
@@ -22,6 +22,7 @@
 use std::str::FromStr;
 
 use bpstd::{Outpoint, OutpointParseError};
+use strict_encoding::StrictSerialize;
 
 use crate::LIB_NAME_SSID;
 
@@ -39,6 +40,8 @@ pub enum Seal {
     Liquid(Outpoint),
 }
 
+impl StrictSerialize for Seal {}
+
 impl FromStr for Seal {
     type Err = OutpointParseError;
 
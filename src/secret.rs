@@ -0,0 +1,80 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory hygiene for heap buffers holding secret key material.
+
+use std::io;
+use std::ops::Deref;
+
+use amplify::confinement::U24;
+use strict_encoding::{DeserializeError, StrictDecode, StrictReader};
+use zeroize::Zeroize;
+
+/// Heap buffer which is zeroized on drop.
+///
+/// With `mlock` feature the memory pages of the buffer are also locked in RAM
+/// for the lifetime of the buffer, preventing them from being swapped to disk.
+/// Since locking works on whole pages, other data sharing a page with the
+/// buffer get unlocked together with it.
+pub(crate) struct SecretBuf<T: Zeroize + AsRef<[u8]>>(T);
+
+impl<T: Zeroize + AsRef<[u8]>> SecretBuf<T> {
+    pub fn new(data: T) -> Self {
+        #[cfg(feature = "mlock")]
+        {
+            let data = data.as_ref();
+            // Failure to lock memory (for instance due to RLIMIT_MEMLOCK) is not
+            // fatal: the buffer is still zeroized on drop.
+            unsafe { memsec::mlock(data.as_ptr() as *mut u8, data.len()) };
+        }
+        Self(data)
+    }
+}
+
+impl<T: Zeroize + AsRef<[u8]>> Deref for SecretBuf<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target { &self.0 }
+}
+
+impl<T: Zeroize + AsRef<[u8]>> Drop for SecretBuf<T> {
+    fn drop(&mut self) {
+        #[cfg(feature = "mlock")]
+        let (ptr, len) = (self.0.as_ref().as_ptr() as *mut u8, self.0.as_ref().len());
+        self.0.zeroize();
+        #[cfg(feature = "mlock")]
+        unsafe {
+            memsec::munlock(ptr, len)
+        };
+    }
+}
+
+/// Deserializes strict-encoded data, unlike `StrictDeserialize` not taking the
+/// ownership of the buffer, such that the caller can zeroize it afterwards.
+pub(crate) fn strict_deserialize_secret<T: StrictDecode>(
+    data: &[u8],
+) -> Result<T, DeserializeError> {
+    let mut cursor = io::Cursor::new(data);
+    let me = T::strict_decode(&mut StrictReader::with(U24, &mut cursor))?;
+    if cursor.position() as usize != data.len() {
+        return Err(DeserializeError::DataNotEntirelyConsumed);
+    }
+    Ok(me)
+}
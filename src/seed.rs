@@ -0,0 +1,195 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic generation of secret keys from seeds and BIP39 mnemonics,
+//! including SLIP-10 hierarchical derivation of child keys.
+
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use amplify::confinement::TinyVec;
+#[cfg(feature = "mnemonic")]
+use bip39::Mnemonic;
+use hmac::{Hmac, Mac};
+#[cfg(feature = "mnemonic")]
+use rand::{thread_rng, Rng};
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::{RistrettoPk, RistrettoSk, Seal, Ssi, LIB_NAME_SSID};
+
+/// HMAC key used by SLIP-10 to produce ed25519 master key from a seed.
+const SLIP10_ED25519_KEY: &[u8] = b"ed25519 seed";
+
+/// Index offset marking hardened derivation.
+pub const HARDENED_INDEX_BOUNDARY: u32 = 1 << 31;
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error)]
+#[display(doc_comments)]
+pub enum DerivationParseError {
+    /// derivation path must start with `m`.
+    NoMaster,
+
+    /// invalid derivation index '{0}'.
+    InvalidIndex(String),
+
+    /// derivation index '{0}' is not hardened, while ed25519 keys support only
+    /// hardened derivation.
+    NonHardened(String),
+
+    /// derivation path can't contain more than 255 steps.
+    TooLong,
+}
+
+/// SLIP-10 derivation path for ed25519 keys.
+///
+/// Since ed25519 supports only hardened derivation, the indexes are stored
+/// without the hardened bit, which is added during the derivation.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct DerivationPath(TinyVec<u32>);
+
+impl DerivationPath {
+    /// Constructs empty path, which corresponds to the master key.
+    pub fn master() -> Self { default!() }
+
+    /// Adds a new hardened derivation step to the path.
+    pub fn push(&mut self, index: u32) -> Result<(), DerivationParseError> {
+        if index >= HARDENED_INDEX_BOUNDARY {
+            return Err(DerivationParseError::InvalidIndex(index.to_string()));
+        }
+        self.0.push(index).map_err(|_| DerivationParseError::TooLong)
+    }
+
+    /// Returns derivation indexes, not including the hardened bit.
+    pub fn indexes(&self) -> &[u32] { self.0.as_slice() }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("m")?;
+        for index in &self.0 {
+            write!(f, "/{index}'")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = DerivationParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut steps = s.split('/');
+        if steps.next() != Some("m") {
+            return Err(DerivationParseError::NoMaster);
+        }
+        let mut path = DerivationPath::master();
+        for step in steps {
+            let index = step
+                .strip_suffix(['\'', 'h'])
+                .ok_or_else(|| DerivationParseError::NonHardened(step.to_owned()))?;
+            let index = u32::from_str(index)
+                .map_err(|_| DerivationParseError::InvalidIndex(step.to_owned()))?;
+            path.push(index)?;
+        }
+        Ok(path)
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut hmac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for chunk in data {
+        hmac.update(chunk);
+    }
+    let mut output = hmac.finalize().into_bytes();
+    let (mut left, mut right) = (Zeroizing::new([0u8; 32]), Zeroizing::new([0u8; 32]));
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output.as_mut_slice().zeroize();
+    (left, right)
+}
+
+impl RistrettoSk {
+    /// Constructs master secret key from a seed (for instance the one produced
+    /// by BIP39 from a mnemonic) according to SLIP-10 for the ed25519 curve.
+    pub fn from_master_seed(seed: &[u8]) -> Self {
+        let (sk, _) = hmac_sha512(SLIP10_ED25519_KEY, &[seed]);
+        Self::from_seed(*sk)
+    }
+
+    /// Derives child secret key from a master seed using SLIP-10 hardened
+    /// derivation for the ed25519 curve.
+    ///
+    /// The derivation path is kept with the key and is reported in the
+    /// secret key bindle headers.
+    pub fn derive(seed: &[u8], path: &DerivationPath) -> Self {
+        let (mut sk, mut chain_code) = hmac_sha512(SLIP10_ED25519_KEY, &[seed]);
+        for index in path.indexes() {
+            let index = (index | HARDENED_INDEX_BOUNDARY).to_be_bytes();
+            (sk, chain_code) = hmac_sha512(&*chain_code, &[&[0u8], &*sk, &index]);
+        }
+        Self::from_seed(*sk).with_derivation(path.clone())
+    }
+
+    /// Constructs master secret key from a BIP39 mnemonic and an optional
+    /// passphrase (use an empty string if no passphrase is used).
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        Self::from_master_seed(Zeroizing::new(mnemonic.to_seed(passphrase)).as_slice())
+    }
+}
+
+impl Ssi<RistrettoPk> {
+    /// Derives identity secret key from a master seed using SLIP-10 hardened
+    /// derivation path and creates identity certificate for it using the
+    /// provided seal.
+    pub fn derive(seed: &[u8], path: &DerivationPath, seal: Seal) -> Self {
+        Self::with(RistrettoSk::derive(seed, path), seal)
+    }
+}
+
+#[cfg(feature = "mnemonic")]
+impl Ssi<RistrettoPk> {
+    /// Generates new identity together with a 24-word BIP39 mnemonic, which can
+    /// be used to restore the identity secret key with [`Ssi::from_mnemonic`].
+    ///
+    /// Use [`DerivationPath::master`] to take the master key of the seed as the
+    /// identity key.
+    pub fn with_mnemonic(seal: Seal, passphrase: &str, path: &DerivationPath) -> (Self, Mnemonic) {
+        let mut entropy = Zeroizing::new([0u8; 32]);
+        thread_rng().fill(&mut entropy[..]);
+        let mnemonic = Mnemonic::from_entropy(&entropy[..]).expect("valid entropy length");
+        (Self::from_mnemonic(&mnemonic, passphrase, path, seal), mnemonic)
+    }
+
+    /// Restores identity secret key from a BIP39 mnemonic, the passphrase and
+    /// the derivation path, and creates identity certificate for it using the
+    /// provided seal.
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        path: &DerivationPath,
+        seal: Seal,
+    ) -> Self {
+        Self::derive(Zeroizing::new(mnemonic.to_seed(passphrase)).as_slice(), path, seal)
+    }
+}
@@ -0,0 +1,176 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Shamir secret sharing of identity secret keys.
+//!
+//! A strict-encoded secret key is split into shares over GF(256), such that
+//! any `threshold` of them recover the key, while fewer shares reveal nothing
+//! about it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::mem;
+
+use amplify::confinement::{Confined, SmallBlob, U24};
+use sharks::{Share, Sharks};
+use strict_encoding::{DeserializeError, StrictDeserialize, StrictSerialize};
+use zeroize::Zeroize;
+
+use crate::secret::{strict_deserialize_secret, SecretBuf};
+use crate::{BindleContent, Fingerprint, Pk, RistrettoPk, LIB_NAME_SSID};
+
+#[derive(Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum ShareError {
+    /// invalid threshold {threshold} for {total} shares; the threshold must be
+    /// at least 2 and must not exceed the number of shares.
+    InvalidThreshold { threshold: u8, total: u8 },
+
+    /// {present} shares are provided, while at least {threshold} are required.
+    NotEnoughShares { present: usize, threshold: u8 },
+
+    /// the provided shares belong to different secret keys or splits.
+    MismatchedShares,
+
+    /// share with index {0} is provided more than once.
+    DuplicateShare(u8),
+
+    /// invalid share data. Details: {0}
+    InvalidShare(String),
+
+    /// recovered secret key can't be deserialized. Details: {0}
+    #[from]
+    Deserialize(DeserializeError),
+
+    /// recovered secret key doesn't match the public key of the shares.
+    KeyMismatch,
+}
+
+/// Share of a secret key.
+///
+/// The share data are wiped on drop.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct SkShare<K: Pk = RistrettoPk> {
+    pk: K,
+    threshold: u8,
+    total: u8,
+    index: u8,
+    data: SmallBlob,
+}
+
+impl<K: Pk> Drop for SkShare<K> {
+    fn drop(&mut self) { mem::take(&mut self.data).release().zeroize() }
+}
+impl<K: Pk> StrictSerialize for SkShare<K> {}
+impl<K: Pk> StrictDeserialize for SkShare<K> {}
+
+impl<K: Pk> BindleContent for SkShare<K> {
+    const MAGIC: [u8; 4] = *b"SSSS";
+    const PLATE_TITLE: &'static str = "SSID SECRET KEY SHARE";
+    type Id = K;
+
+    fn bindle_id(&self) -> Self::Id { self.pk }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        bmap! {
+            "Fingerprint" => self.pk.fingerprint().to_string(),
+            "Share" => format!("{} of {}", self.index, self.total),
+            "Threshold" => self.threshold.to_string()
+        }
+    }
+}
+
+impl<K: Pk> SkShare<K> {
+    /// Splits secret key into `total` shares, any `threshold` of which are
+    /// sufficient to recover the key.
+    pub fn split(sk: &K::Sk, threshold: u8, total: u8) -> Result<Vec<Self>, ShareError> {
+        if threshold < 2 || threshold > total {
+            return Err(ShareError::InvalidThreshold { threshold, total });
+        }
+        let pk = K::with(sk);
+        let secret = SecretBuf::new(sk.to_strict_serialized::<U24>().expect("in-memory").release());
+        let shares = Sharks(threshold)
+            .dealer(&secret)
+            .take(total as usize)
+            .map(|share| {
+                // share data are moved into the share without leaving a copy behind
+                let mut data = Vec::from(&share);
+                let index = data.remove(0);
+                SkShare {
+                    pk,
+                    threshold,
+                    total,
+                    index,
+                    data: Confined::try_from(data).expect("secret key too large"),
+                }
+            })
+            .collect();
+        Ok(shares)
+    }
+
+    /// Recovers secret key from at least `threshold` shares.
+    pub fn combine(shares: &[Self]) -> Result<K::Sk, ShareError> {
+        let first = shares.first().ok_or(ShareError::NotEnoughShares {
+            present: 0,
+            threshold: 2,
+        })?;
+        if shares.len() < first.threshold as usize {
+            return Err(ShareError::NotEnoughShares {
+                present: shares.len(),
+                threshold: first.threshold,
+            });
+        }
+        let mut indexes = BTreeSet::new();
+        let mut parsed = Vec::with_capacity(shares.len());
+        for share in shares {
+            if (share.pk, share.threshold, share.total) != (first.pk, first.threshold, first.total)
+            {
+                return Err(ShareError::MismatchedShares);
+            }
+            if !indexes.insert(share.index) {
+                return Err(ShareError::DuplicateShare(share.index));
+            }
+            let mut data = Vec::with_capacity(share.data.len() + 1);
+            data.push(share.index);
+            data.extend_from_slice(&share.data);
+            let data = SecretBuf::new(data);
+            parsed.push(
+                Share::try_from(data.as_slice())
+                    .map_err(|err| ShareError::InvalidShare(err.to_owned()))?,
+            );
+        }
+        let secret = Sharks(first.threshold)
+            .recover(&parsed)
+            .map(SecretBuf::new)
+            .map_err(|err| ShareError::InvalidShare(err.to_owned()))?;
+        let sk = strict_deserialize_secret::<K::Sk>(&secret)?;
+        if K::with(&sk) != first.pk {
+            return Err(ShareError::KeyMismatch);
+        }
+        Ok(sk)
+    }
+
+    pub fn pk(&self) -> K { self.pk }
+    pub fn fingerprint(&self) -> Fingerprint { self.pk.fingerprint() }
+    pub fn index(&self) -> u8 { self.index }
+    pub fn threshold(&self) -> u8 { self.threshold }
+    pub fn total(&self) -> u8 { self.total }
+}
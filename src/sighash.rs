@@ -0,0 +1,90 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Domain-separated hashes of the data which get signed.
+
+use amplify::Bytes32;
+use commit_verify::{Digest as _, Sha256};
+
+use crate::Digest;
+
+/// Purpose of a signature, defining the tag of the hash which gets signed.
+///
+/// Since a signature always commits to a tagged hash, a signature created for
+/// one purpose can't be replayed as a valid signature for another one.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(lowercase)]
+pub enum SigDomain {
+    /// Self-signature of an identity by its own key.
+    Identity,
+
+    /// Revocation of an identity in favour of a new one.
+    Revocation,
+
+    /// Signature over an arbitrary message digest.
+    Message,
+}
+
+impl SigDomain {
+    /// Tag used in the BIP340-style tagged hash.
+    pub const fn tag(self) -> &'static str {
+        match self {
+            SigDomain::Identity => "SSID/identity",
+            SigDomain::Revocation => "SSID/revocation",
+            SigDomain::Message => "SSID/message",
+        }
+    }
+}
+
+/// Computes BIP340-style tagged hash `SHA256(SHA256(tag) || SHA256(tag) || data)`.
+pub(crate) fn tagged_hash(tag: &str, data: impl AsRef<[u8]>) -> Bytes32 {
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher.update(data);
+    <[u8; 32]>::from(hasher.finalize()).into()
+}
+
+/// BIP340-style tagged hash of the data being signed, computed as
+/// `SHA256(SHA256(tag) || SHA256(tag) || data)`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Display)]
+#[display(inner)]
+pub struct SigHash(Bytes32);
+
+impl SigHash {
+    pub fn with(domain: SigDomain, data: impl AsRef<[u8]>) -> Self {
+        Self(tagged_hash(domain.tag(), data))
+    }
+
+    /// Hash for signing an arbitrary message with the provided digest.
+    pub fn message(digest: impl Into<Digest>) -> Self {
+        Self::with(SigDomain::Message, digest.into())
+    }
+
+    pub fn as_slice(&self) -> &[u8] { self.0.as_slice() }
+
+    pub fn to_byte_array(&self) -> [u8; 32] { self.0.to_byte_array() }
+}
+
+impl AsRef<[u8]> for SigHash {
+    fn as_ref(&self) -> &[u8] { self.as_slice() }
+}
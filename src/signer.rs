@@ -0,0 +1,275 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing abstraction decoupling identity operations from in-memory secret
+//! keys, such that the keys may reside in another process, a smartcard, an HSM
+//! or a remote signing service.
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+use amplify::Bytes32;
+
+use crate::{
+    IdCert, Identity, Pk, RistrettoPk, RistrettoSk, Seal, SigCert, SigHash, Signature, Sk, Ssi,
+};
+
+/// Signature type produced by the key of a [`Signer`].
+pub type SignerSig<S> = <<<S as Signer>::Pk as Pk>::Sk as Sk>::Sig;
+
+/// Source of signatures made by a single key.
+pub trait Signer {
+    type Pk: Pk;
+    type Error: Error;
+
+    /// Public key of the signing key.
+    fn pk(&self) -> Self::Pk;
+
+    /// Signs the tagged hash.
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error>;
+}
+
+impl<S: Signer + ?Sized> Signer for &S {
+    type Pk = S::Pk;
+    type Error = S::Error;
+
+    fn pk(&self) -> Self::Pk { (*self).pk() }
+
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error> {
+        (*self).sign(message)
+    }
+}
+
+/// Signer with the secret key kept in memory.
+pub struct MemSigner<K: Pk = RistrettoPk> {
+    sk: K::Sk,
+    pk: K,
+}
+
+impl<K: Pk> MemSigner<K> {
+    pub fn new(sk: K::Sk) -> Self {
+        let pk = K::with(&sk);
+        MemSigner { sk, pk }
+    }
+
+    pub fn sk(&self) -> &K::Sk { &self.sk }
+
+    pub fn into_sk(self) -> K::Sk { self.sk }
+}
+
+impl From<RistrettoSk> for MemSigner {
+    fn from(sk: RistrettoSk) -> Self { MemSigner::new(sk) }
+}
+
+impl<K: Pk> Signer for MemSigner<K> {
+    type Pk = K;
+    type Error = Infallible;
+
+    fn pk(&self) -> K { self.pk }
+
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error> {
+        Ok(self.sk.sign(message))
+    }
+}
+
+/// Signer borrowing the secret key, used by the constructors which take the
+/// key directly.
+pub(crate) struct SkSigner<'sk, K: Pk> {
+    sk: &'sk K::Sk,
+    _phantom: PhantomData<K>,
+}
+
+impl<'sk, K: Pk> SkSigner<'sk, K> {
+    pub(crate) fn new(sk: &'sk K::Sk) -> Self {
+        SkSigner {
+            sk,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<K: Pk> Signer for SkSigner<'_, K> {
+    type Pk = K;
+    type Error = Infallible;
+
+    fn pk(&self) -> K { K::with(self.sk) }
+
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error> {
+        Ok(self.sk.sign(message))
+    }
+}
+
+/// Every [`Ssi`] is able to sign with the signer holding its current key.
+impl<K: Pk, S: Signer<Pk = K>> Signer for Ssi<K, S> {
+    type Pk = K;
+    type Error = S::Error;
+
+    fn pk(&self) -> K { self.signer.pk() }
+
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error> {
+        self.signer.sign(message)
+    }
+}
+
+/// Test double passing signing requests to an inner signer and recording
+/// them.
+pub struct RecordingSigner<S: Signer> {
+    inner: S,
+    requests: Mutex<Vec<SigHash>>,
+}
+
+impl<S: Signer> RecordingSigner<S> {
+    pub fn new(inner: S) -> Self {
+        RecordingSigner {
+            inner,
+            requests: none!(),
+        }
+    }
+
+    /// Returns all hashes requested to be signed so far, in the order of the
+    /// requests.
+    pub fn requests(&self) -> Vec<SigHash> {
+        self.requests.lock().expect("poisoned request log").clone()
+    }
+
+    pub fn into_inner(self) -> S { self.inner }
+}
+
+impl<S: Signer> Signer for RecordingSigner<S> {
+    type Pk = S::Pk;
+    type Error = S::Error;
+
+    fn pk(&self) -> Self::Pk { self.inner.pk() }
+
+    fn sign(&self, message: SigHash) -> Result<SignerSig<Self>, Self::Error> {
+        self.requests.lock().expect("poisoned request log").push(message);
+        self.inner.sign(message)
+    }
+}
+
+impl<K: Pk> IdCert<K> {
+    /// Creates genesis certificate of the identity controlled by the signer.
+    pub fn with_signer<S: Signer<Pk = K>>(seal: Seal, signer: &S) -> Result<Self, S::Error> {
+        let identity = Identity {
+            key: signer.pk(),
+            seal,
+        };
+        let sig = signer.sign(identity.into())?;
+        Ok(IdCert::new(identity, sig))
+    }
+}
+
+impl<K: Pk> SigCert<K> {
+    /// Signs the message digest with the signer holding the current key of the
+    /// identity.
+    pub fn with_signer<S: Signer<Pk = K>>(
+        digest: impl Into<Bytes32>,
+        signer: &S,
+        id: IdCert<K>,
+    ) -> Result<Self, S::Error> {
+        let digest = digest.into();
+        Ok(SigCert {
+            sig: Signature {
+                digest,
+                sig: signer.sign(SigHash::message(digest))?,
+            },
+            id,
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::asynchronous::AsyncSigner;
+
+#[cfg(feature = "async")]
+mod asynchronous {
+    use async_trait::async_trait;
+
+    use super::*;
+
+    /// Source of signatures which are produced asynchronously, like network
+    /// signing services.
+    ///
+    /// All synchronous [`Signer`]s are asynchronous signers as well.
+    #[async_trait]
+    pub trait AsyncSigner: Sync {
+        type Pk: Pk;
+        type Error: Error;
+
+        /// Public key of the signing key.
+        fn pk(&self) -> Self::Pk;
+
+        /// Signs the tagged hash.
+        async fn sign_async(
+            &self,
+            message: SigHash,
+        ) -> Result<<<Self::Pk as Pk>::Sk as Sk>::Sig, Self::Error>;
+    }
+
+    #[async_trait]
+    impl<S: Signer + Sync> AsyncSigner for S {
+        type Pk = S::Pk;
+        type Error = S::Error;
+
+        fn pk(&self) -> Self::Pk { Signer::pk(self) }
+
+        async fn sign_async(&self, message: SigHash) -> Result<SignerSig<S>, Self::Error> {
+            Signer::sign(self, message)
+        }
+    }
+
+    impl<K: Pk> IdCert<K> {
+        /// Creates genesis certificate of the identity controlled by the
+        /// asynchronous signer.
+        pub async fn with_async_signer<S: AsyncSigner<Pk = K>>(
+            seal: Seal,
+            signer: &S,
+        ) -> Result<Self, S::Error> {
+            let identity = Identity {
+                key: signer.pk(),
+                seal,
+            };
+            let sig = signer.sign_async(identity.into()).await?;
+            Ok(IdCert::new(identity, sig))
+        }
+    }
+
+    impl<K: Pk> SigCert<K> {
+        /// Signs the message digest with the asynchronous signer holding the
+        /// current key of the identity.
+        pub async fn with_async_signer<S: AsyncSigner<Pk = K>>(
+            digest: impl Into<Bytes32>,
+            signer: &S,
+            id: IdCert<K>,
+        ) -> Result<Self, S::Error> {
+            let digest = digest.into();
+            Ok(SigCert {
+                sig: Signature {
+                    digest,
+                    sig: signer.sign_async(SigHash::message(digest)).await?,
+                },
+                id,
+            })
+        }
+    }
+}
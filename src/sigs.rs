@@ -19,9 +19,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
+
 use amplify::Bytes32;
+use strict_encoding::{StrictDeserialize, StrictSerialize};
 
-use crate::{IdCert, Identity, Pk, RistrettoPk, RistrettoSig, Sig, Sk, LIB_NAME_SSID};
+use crate::signer::SkSigner;
+use crate::{
+    BatchVerifyError, BindleContent, IdCert, Identity, Pk, RistrettoPk, RistrettoSig, Sig, SigHash,
+    Sk, LIB_NAME_SSID,
+};
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
@@ -31,6 +38,14 @@ pub struct Signature<S: Sig = RistrettoSig> {
     pub sig: S,
 }
 
+impl<S: Sig> StrictSerialize for Signature<S> {}
+impl<S: Sig> StrictDeserialize for Signature<S> {}
+
+impl<S: Sig> Signature<S> {
+    /// Tagged hash of the message digest, which is actually signed.
+    pub fn sig_hash(&self) -> SigHash { SigHash::message(self.digest) }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 #[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
 #[strict_type(lib = LIB_NAME_SSID)]
@@ -39,6 +54,41 @@ pub struct SigCert<K: Pk = RistrettoPk> {
     pub id: IdCert<K>,
 }
 
+impl<K: Pk> StrictSerialize for SigCert<K> {}
+impl<K: Pk> StrictDeserialize for SigCert<K> {}
+
 impl<K: Pk> SigCert<K> {
+    pub fn new(digest: impl Into<Bytes32>, sk: &K::Sk, id: IdCert<K>) -> Self {
+        Self::with_signer(digest, &SkSigner::new(sk), id).unwrap_or_else(|err| match err {})
+    }
+
     pub fn identity(&self) -> Identity<K> { self.id.identity() }
+
+    /// Checks that the message signature is made by the current identity key.
+    #[must_use]
+    pub fn verify(&self) -> bool { self.identity().key.verify(self.sig.sig_hash(), &self.sig.sig) }
+
+    /// Checks signatures of many certificates at once, reporting indexes of the
+    /// invalid ones.
+    pub fn verify_batch(certs: &[Self]) -> Result<(), BatchVerifyError> {
+        let batch = certs
+            .iter()
+            .map(|cert| (cert.identity().key, cert.sig.sig_hash(), cert.sig.sig))
+            .collect::<Vec<_>>();
+        K::verify_batch(&batch)
+    }
+}
+
+impl<K: Pk> BindleContent for SigCert<K> {
+    const MAGIC: [u8; 4] = *b"SSSG";
+    const PLATE_TITLE: &'static str = "SSID SIGNATURE";
+    type Id = K;
+
+    fn bindle_id(&self) -> Self::Id { self.identity().key }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        bmap! {
+            "Fingerprint" => self.id.fingerprint().to_string(),
+            "Digest" => self.sig.digest.to_string()
+        }
+    }
 }
@@ -0,0 +1,236 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verifiable random function bound to identity keys, implementing
+//! ECVRF-EDWARDS25519-SHA512-TAI ciphersuite from RFC 9381.
+//!
+//! The owner of an identity computes a [`VrfProof`] for some input with
+//! [`RistrettoSk::vrf_prove`]; anybody knowing the identity key may check the
+//! proof and obtain the pseudorandom output with [`RistrettoPk::vrf_verify`].
+//! For a given key and input there is exactly one output, which is
+//! unpredictable without the secret key.
+
+use std::collections::BTreeMap;
+
+use amplify::confinement::SmallBlob;
+use amplify::hex::ToHex;
+use amplify::Bytes64;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use sha2::{Digest, Sha512};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+use zeroize::Zeroizing;
+
+use crate::{BindleContent, IdCert, IdCertError, Pk, RistrettoPk, RistrettoSk, LIB_NAME_SSID};
+
+/// Identifier of the ECVRF-EDWARDS25519-SHA512-TAI ciphersuite.
+const SUITE: u8 = 0x03;
+
+const PROOF_LEN: usize = 80;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Display, Error, From)]
+#[display(doc_comments)]
+pub enum VrfError {
+    /// public key is not a valid VRF key.
+    InvalidKey,
+
+    /// VRF proof is invalid.
+    InvalidProof,
+
+    /// identity certificate of the VRF proof is invalid. Details: {0}
+    #[from]
+    InvalidCert(IdCertError),
+}
+
+/// Pseudorandom output of the VRF.
+pub type VrfOutput = Bytes64;
+
+fn hash(parts: &[&[u8]]) -> [u8; 64] {
+    let mut engine = Sha512::new();
+    for part in parts {
+        engine.update(part);
+    }
+    let mut output = [0u8; 64];
+    output.copy_from_slice(&engine.finalize());
+    output
+}
+
+/// Maps the input to a curve point using the try-and-increment method.
+fn encode_to_curve(pk: &[u8; 32], alpha: &[u8]) -> EdwardsPoint {
+    for ctr in 0..=u8::MAX {
+        let hash = hash(&[&[SUITE, 0x01], pk, alpha, &[ctr, 0x00]]);
+        let mut candidate = [0u8; 32];
+        candidate.copy_from_slice(&hash[..32]);
+        if let Some(point) = CompressedEdwardsY(candidate).decompress() {
+            return point.mul_by_cofactor();
+        }
+    }
+    // Each iteration succeeds with probability of 1/2
+    unreachable!("no curve point found in 256 attempts")
+}
+
+fn challenge(points: [&EdwardsPoint; 5]) -> [u8; 16] {
+    let mut engine = Sha512::new();
+    engine.update([SUITE, 0x02]);
+    for point in points {
+        engine.update(point.compress().as_bytes());
+    }
+    engine.update([0x00]);
+    let mut c = [0u8; 16];
+    c.copy_from_slice(&engine.finalize()[..16]);
+    c
+}
+
+fn challenge_scalar(c: [u8; 16]) -> Scalar {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&c);
+    Scalar::from_bytes_mod_order(bytes)
+}
+
+/// Proof of the VRF output for some input, produced by an identity key.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct VrfProof([u8; PROOF_LEN]);
+
+impl VrfProof {
+    pub fn from_byte_array(data: [u8; PROOF_LEN]) -> Self { Self(data) }
+    pub fn to_byte_array(&self) -> [u8; PROOF_LEN] { self.0 }
+
+    fn gamma(&self) -> Result<EdwardsPoint, VrfError> {
+        let mut gamma = [0u8; 32];
+        gamma.copy_from_slice(&self.0[..32]);
+        CompressedEdwardsY(gamma).decompress().ok_or(VrfError::InvalidProof)
+    }
+
+    /// Computes the VRF output from the proof.
+    ///
+    /// The output is meaningful only if the proof is verified with
+    /// [`RistrettoPk::vrf_verify`], which returns the same output.
+    pub fn output(&self) -> Result<VrfOutput, VrfError> {
+        let gamma = self.gamma()?.mul_by_cofactor();
+        Ok(hash(&[&[SUITE, 0x03], gamma.compress().as_bytes(), &[0x00]]).into())
+    }
+}
+
+impl RistrettoSk {
+    /// Produces the VRF proof for the `alpha` input.
+    pub fn vrf_prove(&self, alpha: impl AsRef<[u8]>) -> VrfProof {
+        let alpha = alpha.as_ref();
+        let (x, prefix) = self.to_expanded();
+
+        let pk = RistrettoPk::with(self).to_byte_array();
+        let h = encode_to_curve(&pk, alpha);
+        let nonce = Zeroizing::new(hash(&[prefix.as_slice(), h.compress().as_bytes()]));
+        let k = Zeroizing::new(Scalar::from_bytes_mod_order_wide(&nonce));
+
+        let y = EdwardsPoint::mul_base(&x);
+        let gamma = h * *x;
+        let c = challenge([&y, &h, &gamma, &EdwardsPoint::mul_base(&k), &(h * *k)]);
+        let s = *k + challenge_scalar(c) * *x;
+
+        let mut proof = [0u8; PROOF_LEN];
+        proof[..32].copy_from_slice(gamma.compress().as_bytes());
+        proof[32..48].copy_from_slice(&c);
+        proof[48..].copy_from_slice(s.as_bytes());
+        VrfProof(proof)
+    }
+}
+
+impl RistrettoPk {
+    /// Verifies the VRF proof for the `alpha` input, returning the VRF output.
+    pub fn vrf_verify(
+        &self,
+        alpha: impl AsRef<[u8]>,
+        proof: &VrfProof,
+    ) -> Result<VrfOutput, VrfError> {
+        let pk = self.to_byte_array();
+        let y = CompressedEdwardsY(pk)
+            .decompress()
+            .filter(|y| !y.is_small_order())
+            .ok_or(VrfError::InvalidKey)?;
+
+        let gamma = proof.gamma()?;
+        let mut c = [0u8; 16];
+        c.copy_from_slice(&proof.0[32..48]);
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&proof.0[48..]);
+        let s = Option::<Scalar>::from(Scalar::from_canonical_bytes(s))
+            .ok_or(VrfError::InvalidProof)?;
+
+        let h = encode_to_curve(&pk, alpha.as_ref());
+        let c_scalar = challenge_scalar(c);
+        let u = EdwardsPoint::mul_base(&s) - y * c_scalar;
+        let v = h * s - gamma * c_scalar;
+        if challenge([&y, &h, &gamma, &u, &v]) != c {
+            return Err(VrfError::InvalidProof);
+        }
+        proof.output()
+    }
+}
+
+/// VRF proof for some input published by an identity, which may be checked by
+/// anybody against the identity certificate.
+#[derive(Clone, Eq, PartialEq, Debug)]
+#[derive(StrictType, StrictDumb, StrictEncode, StrictDecode)]
+#[strict_type(lib = LIB_NAME_SSID)]
+pub struct VrfCert {
+    pub alpha: SmallBlob,
+    pub proof: VrfProof,
+    pub id: IdCert<RistrettoPk>,
+}
+
+impl StrictSerialize for VrfCert {}
+impl StrictDeserialize for VrfCert {}
+
+impl VrfCert {
+    pub fn new(alpha: SmallBlob, sk: &RistrettoSk, id: IdCert<RistrettoPk>) -> Self {
+        VrfCert {
+            proof: sk.vrf_prove(alpha.as_slice()),
+            alpha,
+            id,
+        }
+    }
+
+    /// Checks the identity certificate and the proof against the current
+    /// identity key, returning the VRF output.
+    pub fn verify(&self) -> Result<VrfOutput, VrfError> {
+        self.id.verify()?;
+        self.id.identity().key.vrf_verify(self.alpha.as_slice(), &self.proof)
+    }
+}
+
+impl BindleContent for VrfCert {
+    const MAGIC: [u8; 4] = *b"SSVR";
+    const PLATE_TITLE: &'static str = "SSID VRF PROOF";
+    type Id = RistrettoPk;
+
+    fn bindle_id(&self) -> Self::Id { self.id.identity().key }
+    fn bindle_headers(&self) -> BTreeMap<&'static str, String> {
+        let mut headers = bmap! {
+            "Fingerprint" => self.id.fingerprint().to_string()
+        };
+        if let Ok(output) = self.proof.output() {
+            headers.insert("Output", output.to_hex());
+        }
+        headers
+    }
+}
@@ -0,0 +1,84 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key agreement between identities.
+
+use amplify::confinement::U16;
+use ssid::{
+    AgreementError, AnyPk, AnySk, EcdsaPk, EcdsaSk, KeyAgreement, Pk, RistrettoSk, Secp256k1Pk,
+    Secp256k1Sk, Sk,
+};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+fn check_symmetry<K: KeyAgreement>() {
+    let (a, b) = (K::Sk::generate(), K::Sk::generate());
+    let ab = K::with(&b).agree(&a).unwrap();
+    let ba = K::with(&a).agree(&b).unwrap();
+    assert_eq!(*ab, *ba);
+    let c = K::Sk::generate();
+    assert_ne!(*K::with(&c).agree(&a).unwrap(), *ab);
+}
+
+#[test]
+fn symmetry() {
+    check_symmetry::<ssid::RistrettoPk>();
+    check_symmetry::<Secp256k1Pk>();
+    check_symmetry::<EcdsaPk>();
+    check_symmetry::<AnyPk>();
+}
+
+#[test]
+fn any_algo_mismatch() {
+    let ristretto = AnySk::from(RistrettoSk::generate());
+    let secp256k1 = AnySk::from(Secp256k1Sk::generate());
+    assert_eq!(
+        AnyPk::with(&ristretto).agree(&secp256k1).unwrap_err(),
+        AgreementError::AlgoMismatch
+    );
+    assert_eq!(
+        AnyPk::with(&secp256k1).agree(&ristretto).unwrap_err(),
+        AgreementError::AlgoMismatch
+    );
+    let bip340 = AnyPk::with(&secp256k1);
+    let ecdsa = AnySk::from(to_ecdsa(&Secp256k1Sk::generate()));
+    assert_eq!(bip340.agree(&ecdsa).unwrap_err(), AgreementError::AlgoMismatch);
+}
+
+/// Converts BIP340 secret key into ECDSA one; both are the same secp256k1
+/// scalar.
+fn to_ecdsa(sk: &Secp256k1Sk) -> EcdsaSk {
+    EcdsaSk::from_strict_serialized::<U16>(sk.to_strict_serialized::<U16>().unwrap()).unwrap()
+}
+
+#[test]
+fn secp256k1_ecdsa_compatibility() {
+    let (a, b) = (Secp256k1Sk::generate(), Secp256k1Sk::generate());
+    let bip340 = Secp256k1Pk::with(&b).agree(&a).unwrap();
+    let ecdsa = EcdsaPk::with(&to_ecdsa(&b)).agree(&to_ecdsa(&a)).unwrap();
+    assert_eq!(*bip340, *ecdsa);
+}
+
+#[cfg(feature = "p256")]
+#[test]
+fn any_unsupported() {
+    let sk = AnySk::from(ssid::P256Sk::generate());
+    assert_eq!(AnyPk::with(&sk).agree(&sk).unwrap_err(), AgreementError::Unsupported);
+}
@@ -0,0 +1,70 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime-dispatched keys and certificates of mixed algorithms.
+
+mod common;
+
+use std::str::FromStr;
+
+use common::{seal, Payload};
+use ssid::{AnyPk, IdCert, Identity, Pk, RistrettoPk, Secp256k1Pk, SigHash, Ssi};
+
+#[test]
+fn pk_baid58() {
+    let ristretto = Ssi::<RistrettoPk>::new(seal()).cert.identity().key;
+    let bip340 = Ssi::<Secp256k1Pk>::new(seal()).cert.identity().key;
+    assert_eq!(AnyPk::from_str(&ristretto.to_string()), Ok(AnyPk::Ristretto(ristretto)));
+    assert_eq!(AnyPk::from_str(&bip340.to_string()), Ok(AnyPk::Bip340(bip340)));
+}
+
+#[test]
+fn pk_invalid() {
+    let unknown = Payload([0xFF; 33]).to_pk_string();
+    assert!(AnyPk::from_str(&unknown).is_err());
+
+    // x-only key which is not on the curve
+    let mut data = [0xFF; 33];
+    data[0] = Secp256k1Pk::ID;
+    let off_curve = Payload(data).to_pk_string();
+    assert!(AnyPk::from_str(&off_curve).is_err());
+}
+
+#[test]
+fn identity_hashes() {
+    let id = Ssi::<Secp256k1Pk>::new(seal()).cert.identity();
+    let new = Identity {
+        key: Ssi::<Secp256k1Pk>::new(seal()).cert.identity().key,
+        seal: seal(),
+    };
+    assert_eq!(SigHash::from(id.into_any()), SigHash::from(id));
+    assert_eq!(id.into_any().revocation_commitment(new.into_any()), id.revocation_commitment(new));
+    assert_eq!(id.into_any().long_fingerprint(), id.long_fingerprint());
+}
+
+#[test]
+fn cert_into_any() {
+    let ristretto = Ssi::<RistrettoPk>::new(seal()).cert.into_any();
+    let bip340 = Ssi::<Secp256k1Pk>::new(seal()).cert.into_any();
+    assert_eq!(ristretto.verify(), Ok(()));
+    assert_eq!(bip340.verify(), Ok(()));
+    assert_eq!(IdCert::verify_sigs_batch(&[ristretto, bip340]), Ok(()));
+}
@@ -0,0 +1,162 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batch verification reporting the invalid entries.
+
+mod common;
+
+use common::seal;
+use ssid::{
+    AnyPk, AnySk, BatchItem, BatchVerifyError, IdCert, Pk, Proof, Revocation, RistrettoPk,
+    RistrettoSk, Secp256k1Sk, SigCert, SigHash, Sk, Ssi,
+};
+use strict_encoding::StrictDumb;
+
+const MESSAGE: [u8; 32] = [0xA5; 32];
+const OTHER: [u8; 32] = [0x5A; 32];
+
+/// Batch entry signed either over the verified message, or over the other one.
+fn item<K: Pk>(sk: &K::Sk, valid: bool) -> BatchItem<K> {
+    let message = SigHash::message(MESSAGE);
+    let signed = if valid { message } else { SigHash::message(OTHER) };
+    (K::with(sk), message, sk.sign(signed))
+}
+
+#[test]
+fn empty() {
+    assert_eq!(RistrettoPk::verify_batch(&[]), Ok(()));
+    assert_eq!(AnyPk::verify_batch(&[]), Ok(()));
+}
+
+#[test]
+fn mixed() {
+    let sk = RistrettoSk::generate();
+    let batch = [true, false, true, true, false].map(|valid| item::<RistrettoPk>(&sk, valid));
+    assert_eq!(RistrettoPk::verify_batch(&batch[..1]), Ok(()));
+    assert_eq!(
+        RistrettoPk::verify_batch(&batch),
+        Err(BatchVerifyError {
+            invalid: vec![1, 4]
+        })
+    );
+}
+
+#[test]
+fn any_mixed() {
+    let sks = [AnySk::from(RistrettoSk::generate()), AnySk::from(Secp256k1Sk::generate())];
+    let mut batch = vec![
+        item::<AnyPk>(&sks[0], true),
+        item::<AnyPk>(&sks[1], false),
+        item::<AnyPk>(&sks[1], true),
+    ];
+    // signature of a different algorithm
+    batch.push((batch[0].0, batch[0].1, batch[2].2));
+    assert_eq!(
+        AnyPk::verify_batch(&batch),
+        Err(BatchVerifyError {
+            invalid: vec![1, 3]
+        })
+    );
+}
+
+#[cfg(feature = "bls")]
+#[test]
+fn bls_mixed() {
+    use ssid::{BlsPk, BlsSk};
+
+    let sk = BlsSk::generate();
+    let batch = [false, true, true, false].map(|valid| item::<BlsPk>(&sk, valid));
+    assert_eq!(BlsPk::verify_batch(&batch[1..3]), Ok(()));
+    assert_eq!(
+        BlsPk::verify_batch(&batch),
+        Err(BatchVerifyError {
+            invalid: vec![0, 3]
+        })
+    );
+}
+
+/// BLS entries are verified as a separate batch, and their indexes must be
+/// mapped back to the positions in the whole batch.
+#[cfg(feature = "bls")]
+#[test]
+fn any_bls_mixed() {
+    use ssid::BlsSk;
+
+    let sks = [AnySk::from(RistrettoSk::generate()), AnySk::from(BlsSk::generate())];
+    let batch = [(0, true), (1, true), (0, false), (1, false), (1, true), (0, true), (1, false)]
+        .map(|(no, valid)| item::<AnyPk>(&sks[no], valid));
+    assert_eq!(
+        AnyPk::verify_batch(&batch),
+        Err(BatchVerifyError {
+            invalid: vec![2, 3, 6]
+        })
+    );
+    let valid = [batch[0], batch[1], batch[4], batch[5]];
+    assert_eq!(AnyPk::verify_batch(&valid), Ok(()));
+}
+
+/// Appends revocations, which are not covered by the signature batch.
+fn revoke(cert: &mut IdCert, count: usize) {
+    for _ in 0..count {
+        let revocation = Revocation {
+            new_identity: Ssi::<RistrettoPk>::new(seal()).cert.identity(),
+            revocation_proof: Proof::strict_dumb(),
+        };
+        cert.revocations.push(revocation).unwrap();
+    }
+}
+
+#[test]
+fn certs() {
+    let mut certs = (0..5).map(|_| Ssi::<RistrettoPk>::new(seal()).cert).collect::<Vec<_>>();
+    revoke(&mut certs[2], 3);
+    revoke(&mut certs[3], 2);
+    revoke(&mut certs[4], 1);
+    assert_eq!(IdCert::verify_sigs_batch(&certs), Ok(()));
+
+    certs[1].genesis_sig = certs[0].genesis_sig;
+    certs[3].genesis_sig = certs[2].genesis_sig;
+    assert_eq!(
+        IdCert::verify_sigs_batch(&certs),
+        Err(BatchVerifyError {
+            invalid: vec![1, 3]
+        })
+    );
+}
+
+#[test]
+fn sig_certs() {
+    let ssis = (0..3).map(|_| Ssi::<RistrettoPk>::new(seal())).collect::<Vec<_>>();
+    let mut sigs = ssis
+        .iter()
+        .map(|ssi| SigCert::new(MESSAGE, ssi.sk(), ssi.cert.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(SigCert::verify_batch(&sigs), Ok(()));
+
+    sigs[2].sig.digest = OTHER.into();
+    sigs[0].id = ssis[1].cert.clone();
+    assert_eq!(
+        SigCert::verify_batch(&sigs),
+        Err(BatchVerifyError {
+            invalid: vec![0, 2]
+        })
+    );
+}
@@ -0,0 +1,48 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BIP340 Schnorr keys, signatures and identities over secp256k1.
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::U16;
+use common::{check_cert_encoding, check_identity, check_pk_baid58, check_sign_verify};
+use ssid::{Pk, RistrettoPk, RistrettoSk, Secp256k1Pk, Sk};
+
+#[test]
+fn pk_baid58() { check_pk_baid58::<Secp256k1Pk>() }
+
+#[test]
+fn sign_verify() { check_sign_verify::<Secp256k1Pk>() }
+
+#[test]
+fn identity() { check_identity::<Secp256k1Pk>() }
+
+#[test]
+fn cert_encoding() { check_cert_encoding::<Secp256k1Pk, U16>() }
+
+#[test]
+fn pk_other_algo() {
+    let pk = RistrettoPk::with(&RistrettoSk::generate());
+    assert!(Secp256k1Pk::from_str(&pk.to_string()).is_err());
+}
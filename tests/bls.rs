@@ -0,0 +1,137 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 keys and aggregate signatures.
+
+#![cfg(feature = "bls")]
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::{Confined, U16};
+use common::{seal, Payload};
+use ssid::{
+    BlsAggregateError, BlsAggregateSig, BlsPk, BlsSig, BlsSk, Pk, RistrettoPk, RistrettoSk,
+    SigCert, Signature, Sk, Ssi,
+};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+const DIGEST: [u8; 32] = [0xA5; 32];
+
+fn signers(n: usize) -> Vec<Ssi<BlsPk>> { (0..n).map(|_| Ssi::new(seal())).collect() }
+
+fn sign(ssi: &Ssi<BlsPk>) -> SigCert<BlsPk> { SigCert::new(DIGEST, ssi.sk(), ssi.cert.clone()) }
+
+fn sig_bytes(sig: &Signature<BlsSig>) -> Vec<u8> {
+    sig.to_strict_serialized::<U16>().unwrap().release()[32..].to_vec()
+}
+
+#[test]
+fn pk_baid58() {
+    let pk = BlsPk::with(&BlsSk::generate());
+    assert_eq!(BlsPk::from_str(&pk.to_string()), Ok(pk));
+    let other = RistrettoPk::with(&RistrettoSk::generate());
+    assert!(BlsPk::from_str(&other.to_string()).is_err());
+
+    // compressed point flag with x coordinate not on the curve
+    let mut data = [0xFFu8; 49];
+    data[0] = BlsPk::ID;
+    data[1] = 0x9F;
+    let invalid = Payload(data).to_pk_string();
+    assert!(BlsPk::from_str(&invalid).is_err());
+}
+
+#[test]
+fn aggregate_round_trip() {
+    let ssis = signers(3);
+    let sigs = ssis.iter().map(sign).collect::<Vec<_>>();
+    let agg = BlsAggregateSig::aggregate(&sigs).unwrap();
+    assert_eq!(agg.digest(), DIGEST.into());
+    assert!(agg.verify());
+
+    let data = agg.to_strict_serialized::<U16>().unwrap();
+    assert_eq!(BlsAggregateSig::from_strict_serialized::<U16>(data).unwrap(), agg);
+}
+
+#[test]
+fn tampered_digest() {
+    let sigs = signers(2).iter().map(sign).collect::<Vec<_>>();
+    let mut agg = BlsAggregateSig::aggregate(&sigs).unwrap();
+    agg.sig.digest = [0x5A; 32].into();
+    assert!(!agg.verify());
+
+    let mut sigs = sigs;
+    sigs[1].sig.digest = [0x5A; 32].into();
+    assert_eq!(
+        BlsAggregateSig::aggregate(&sigs).unwrap_err(),
+        BlsAggregateError::DigestMismatch(1)
+    );
+}
+
+#[test]
+fn missing_signer() {
+    let ssis = signers(3);
+    let sigs = ssis.iter().map(sign).collect::<Vec<_>>();
+    let mut agg = BlsAggregateSig::aggregate(&sigs).unwrap();
+    agg.signers = Confined::try_from(vec![ssis[0].cert.clone(), ssis[1].cert.clone()]).unwrap();
+    assert!(!agg.verify());
+}
+
+#[test]
+fn duplicate_signer() {
+    let ssis = signers(2);
+    let (a, b) = (sign(&ssis[0]), sign(&ssis[1]));
+    assert_eq!(
+        BlsAggregateSig::aggregate(&[a.clone(), b.clone(), a.clone()]).unwrap_err(),
+        BlsAggregateError::DuplicateSigner(2)
+    );
+
+    // 2·σA + σB is a valid aggregate for the signers A, A and B
+    let (sig_a, sig_b) = (sig_bytes(&a.sig), sig_bytes(&b.sig));
+    let points =
+        [&sig_a, &sig_a, &sig_b].map(|sig| blst::min_pk::Signature::from_bytes(sig).unwrap());
+    let sig = blst::min_pk::AggregateSignature::aggregate(&points.iter().collect::<Vec<_>>(), true)
+        .unwrap()
+        .to_signature()
+        .compress();
+    let data = Confined::try_from([&DIGEST[..], &sig[..]].concat()).unwrap();
+    let agg = BlsAggregateSig {
+        sig: Signature::from_strict_serialized::<U16>(data).unwrap(),
+        signers: Confined::try_from(vec![a.id.clone(), a.id, b.id]).unwrap(),
+    };
+    assert!(!agg.verify());
+}
+
+#[test]
+fn rogue_key() {
+    let ssis = signers(2);
+    let sigs = ssis.iter().map(sign).collect::<Vec<_>>();
+    let mut agg = BlsAggregateSig::aggregate(&sigs).unwrap();
+    assert!(agg.verify());
+
+    // the aggregate is valid for the keys, but the second signer doesn't prove
+    // the possession of its key
+    let mut rogue = ssis[1].cert.clone();
+    rogue.genesis_sig = ssis[0].cert.genesis_sig;
+    agg.signers = Confined::try_from(vec![ssis[0].cert.clone(), rogue]).unwrap();
+    assert!(!agg.verify());
+}
@@ -0,0 +1,113 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixtures shared by the integration tests.
+
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+use baid58::{Chunking, ToBaid58, CHUNKING_32};
+use bpstd::{ScriptPubkey, TxIn, TxOut, UnsignedTx};
+use ssid::{Bindle, BindleContent, BpProof, IdCert, Identity, Pk, Proof, Seal, SigHash, Sk, Ssi};
+use strict_encoding::{StrictDeserialize, StrictDumb, StrictSerialize};
+
+/// Seal used by the genesis identities in the tests.
+pub const SEAL: &str = "bitcoin:1111111111111111111111111111111111111111111111111111111111111111:0";
+
+pub fn seal() -> Seal { Seal::from_str(SEAL).unwrap() }
+
+/// Seal defined by the output `vout` of the seal transaction used by
+/// [`closing_proof`], such that it can be closed in the tests.
+pub fn closable_seal(vout: u32) -> Seal {
+    Seal::from_str(&format!("bitcoin:{}:{vout}", UnsignedTx::strict_dumb().txid())).unwrap()
+}
+
+/// Constructs proof closing the seal of the `prev` identity and committing to
+/// its revocation in favour of the `new` identity.
+pub fn closing_proof<K: Pk>(prev: Identity<K>, new: Identity<K>) -> Proof {
+    let (Seal::Bitcoin(outpoint) | Seal::Liquid(outpoint)) = prev.seal;
+    let mut input = TxIn::strict_dumb();
+    input.prev_output = outpoint;
+    let mut output = TxOut::strict_dumb();
+    output.script_pubkey = ScriptPubkey::op_return(prev.revocation_commitment(new).as_slice());
+    let mut bp = BpProof::strict_dumb();
+    bp.witness_tx.inputs.push(input).unwrap();
+    bp.witness_tx.outputs.push(output).unwrap();
+    match prev.seal {
+        Seal::Bitcoin(_) => Proof::Bitcoin(bp),
+        Seal::Liquid(_) => Proof::Liquid(bp),
+    }
+}
+
+/// Public key payload with arbitrary content.
+pub struct Payload<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> ToBaid58<N> for Payload<N> {
+    const HRI: &'static str = "ssi";
+    const CHUNKING: Option<Chunking> = CHUNKING_32;
+    fn to_baid58_payload(&self) -> [u8; N] { self.0 }
+}
+
+impl<const N: usize> Payload<N> {
+    /// Formats the payload the same way as the public keys are formatted.
+    pub fn to_pk_string(&self) -> String { format!("{::<#.2}", self.to_baid58()) }
+}
+
+/// Checks that the public key is parsed back from its string representation.
+pub fn check_pk_baid58<K: Pk>() {
+    let pk = K::with(&K::Sk::generate());
+    assert_eq!(K::from_str(&pk.to_string()), Ok(pk));
+}
+
+/// Checks that the signature is valid only for the signed message and the
+/// signing key.
+pub fn check_sign_verify<K: Pk>() {
+    let sk = K::Sk::generate();
+    let pk = K::with(&sk);
+    let message = SigHash::message([0xA5; 32]);
+    let sig = sk.sign(message);
+    assert!(pk.verify(message, &sig));
+    assert!(!pk.verify(SigHash::message([0x5A; 32]), &sig));
+    assert!(!K::with(&K::Sk::generate()).verify(message, &sig));
+}
+
+/// Checks that the genesis identity is signed by its own key.
+pub fn check_identity<K: Pk>() {
+    let ssi = Ssi::<K>::new(seal());
+    let genesis = ssi.cert.genesis_id;
+    assert_eq!(genesis.key, K::with(ssi.sk()));
+    assert!(genesis.key.verify(genesis, &ssi.cert.genesis_sig));
+    assert_eq!(ssi.cert.verify(), Ok(()));
+}
+
+/// Checks that the identity certificate is restored from its binary encoding,
+/// which must fit `MAX` bytes, and from its bindle.
+pub fn check_cert_encoding<K: Pk, const MAX: usize>() {
+    let cert = Ssi::<K>::new(seal()).cert;
+    let data = cert.to_strict_serialized::<MAX>().unwrap();
+    assert_eq!(IdCert::from_strict_serialized::<MAX>(data).unwrap(), cert);
+
+    let bindle = cert.clone().bindle();
+    let restored = Bindle::<IdCert<K>>::from_str(&bindle.to_string()).unwrap();
+    assert_eq!(restored.id(), cert.identity().key);
+    assert_eq!(restored.unbindle(), cert);
+}
@@ -0,0 +1,74 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Key mnemonics and safety numbers.
+
+mod common;
+
+use std::str::FromStr;
+
+use common::seal;
+use ssid::{
+    AnyPk, Identity, KeyMnemonic, KeyMnemonicParseError, Pk, RistrettoPk, RistrettoSk,
+    SafetyNumber, Sk, Ssi, SAFETY_NUMBER_GROUPS,
+};
+
+fn identity() -> Identity { Ssi::<RistrettoPk>::new(seal()).cert.identity() }
+
+#[test]
+fn safety_number_symmetry() {
+    let (a, b, c) = (identity(), identity(), identity());
+    let ab = SafetyNumber::with(&a, &b);
+    assert_eq!(ab, SafetyNumber::with(&b, &a));
+    assert_ne!(ab, SafetyNumber::with(&a, &c));
+    assert_ne!(ab, SafetyNumber::with(&c, &b));
+    assert!(ab.groups().iter().all(|group| *group < 100_000));
+    assert_eq!(ab.to_string().len(), SAFETY_NUMBER_GROUPS * 6 - 1);
+}
+
+#[test]
+fn safety_number_any() {
+    let (a, b) = (identity(), identity());
+    assert_eq!(SafetyNumber::with(&a.into_any(), &b.into_any()), SafetyNumber::with(&a, &b));
+}
+
+#[test]
+fn mnemonic_parse() {
+    let words = KeyMnemonic::from_str("  Gray-pagoda_PABLO\tdisco ").unwrap();
+    assert_eq!(words.words(), &["gray", "pagoda", "pablo", "disco"]);
+    assert_eq!(words.to_string(), "gray-pagoda-pablo-disco");
+    assert_eq!(KeyMnemonic::from_str(" - _ "), Err(KeyMnemonicParseError::Empty));
+    assert_eq!(
+        KeyMnemonic::from_str("gray pag0da"),
+        Err(KeyMnemonicParseError::InvalidWord("pag0da".to_owned()))
+    );
+}
+
+#[test]
+fn mnemonic_matches() {
+    let keys = (0..4).map(|_| RistrettoPk::with(&RistrettoSk::generate())).collect::<Vec<_>>();
+    let mnemonic = keys[2].to_mnemonic();
+    assert!(!mnemonic.is_empty());
+    let words = KeyMnemonic::from_str(&mnemonic.to_uppercase()).unwrap();
+    assert!(words.matches(&keys[2]));
+    assert!(words.matches(&AnyPk::from(keys[2])));
+    assert_eq!(words.find(&keys), vec![&keys[2]]);
+}
@@ -0,0 +1,54 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ECDSA keys, signatures and identities over secp256k1.
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::U16;
+use common::{check_cert_encoding, check_identity, check_pk_baid58, check_sign_verify, Payload};
+use ssid::{EcdsaPk, Pk, Secp256k1Pk, Secp256k1Sk, Sk};
+
+#[test]
+fn pk_baid58() { check_pk_baid58::<EcdsaPk>() }
+
+#[test]
+fn sign_verify() { check_sign_verify::<EcdsaPk>() }
+
+#[test]
+fn identity() { check_identity::<EcdsaPk>() }
+
+#[test]
+fn cert_encoding() { check_cert_encoding::<EcdsaPk, U16>() }
+
+#[test]
+fn pk_invalid() {
+    let pk = Secp256k1Pk::with(&Secp256k1Sk::generate());
+    assert!(EcdsaPk::from_str(&pk.to_string()).is_err());
+
+    let mut data = [0xFFu8; 34];
+    data[0] = EcdsaPk::ID;
+    data[1] = 0x02;
+    let off_curve = Payload(data).to_pk_string();
+    assert!(EcdsaPk::from_str(&off_curve).is_err());
+}
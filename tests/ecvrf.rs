@@ -0,0 +1,80 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ECVRF-EDWARDS25519-SHA512-TAI test vector from RFC 9381 appendix B.3.
+
+#![cfg(feature = "vrf")]
+
+mod common;
+
+use amplify::confinement::Confined;
+use amplify::hex::FromHex;
+use common::seal;
+use ssid::{IdCertError, Pk, RistrettoPk, RistrettoSk, Ssi, VrfCert, VrfError, VrfProof};
+
+const SK: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const PK: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+const PI: &str = "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee\
+                  1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805";
+const BETA: &str = "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59\
+                    d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae";
+
+#[test]
+fn prove_verify() {
+    let sk = RistrettoSk::from_seed(<[u8; 32]>::from_hex(SK).unwrap());
+    let pk = RistrettoPk::with(&sk);
+    assert_eq!(pk.to_byte_array(), <[u8; 32]>::from_hex(PK).unwrap());
+
+    let proof = sk.vrf_prove(b"");
+    assert_eq!(proof.to_byte_array().to_vec(), Vec::<u8>::from_hex(PI).unwrap());
+
+    let beta = Vec::<u8>::from_hex(BETA).unwrap();
+    assert_eq!(pk.vrf_verify(b"", &proof).unwrap().to_vec(), beta);
+    assert_eq!(proof.output().unwrap().to_vec(), beta);
+}
+
+#[test]
+fn invalid_proof() {
+    let sk = RistrettoSk::from_seed(<[u8; 32]>::from_hex(SK).unwrap());
+    let pk = RistrettoPk::with(&sk);
+    let proof = sk.vrf_prove(b"lottery round 1");
+    assert_eq!(pk.vrf_verify(b"lottery round 2", &proof), Err(VrfError::InvalidProof));
+
+    let mut data = proof.to_byte_array();
+    data[40] ^= 1;
+    assert_eq!(
+        pk.vrf_verify(b"lottery round 1", &VrfProof::from_byte_array(data)),
+        Err(VrfError::InvalidProof)
+    );
+}
+
+#[test]
+fn cert() {
+    let ssi = Ssi::<RistrettoPk>::new(seal());
+    let alpha = Confined::try_from(b"lottery round 1".to_vec()).unwrap();
+    let cert = VrfCert::new(alpha, ssi.sk(), ssi.cert.clone());
+    assert_eq!(cert.verify(), cert.proof.output());
+
+    // the proof is valid for the key, but the certificate isn't signed by it
+    let mut forged = cert.clone();
+    forged.id.genesis_sig = Ssi::<RistrettoPk>::new(seal()).cert.genesis_sig;
+    assert_eq!(forged.verify(), Err(VrfError::InvalidCert(IdCertError::GenesisSig)));
+}
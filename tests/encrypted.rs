@@ -0,0 +1,102 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use amplify::confinement::{Confined, U24};
+use ssid::{EncryptedSk, KdfParams, Pk, RistrettoPk, RistrettoSk, Sk, UnlockError, KDF_MAX_M_COST};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+/// Cheap parameters to keep the tests fast.
+fn kdf() -> KdfParams {
+    KdfParams {
+        salt: [7u8; 16],
+        m_cost: 64,
+        t_cost: 1,
+        p_cost: 1,
+    }
+}
+
+fn encrypt(sk: &RistrettoSk, passphrase: &str) -> EncryptedSk {
+    EncryptedSk::encrypt_with(sk, passphrase, kdf()).unwrap()
+}
+
+fn modify(enc: &EncryptedSk, f: impl FnOnce(&mut Vec<u8>)) -> EncryptedSk {
+    let mut data = enc.to_strict_serialized::<U24>().unwrap().release();
+    f(&mut data);
+    EncryptedSk::from_strict_serialized::<U24>(Confined::try_from(data).unwrap()).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let sk = RistrettoSk::generate();
+    let enc = encrypt(&sk, "secret");
+    assert_eq!(enc.pk(), RistrettoPk::with(&sk));
+    assert_eq!(RistrettoPk::with(&enc.unlock("secret").unwrap()), RistrettoPk::with(&sk));
+}
+
+#[test]
+fn wrong_passphrase() {
+    let enc = encrypt(&RistrettoSk::generate(), "secret");
+    assert_eq!(enc.unlock("Secret").unwrap_err(), UnlockError::Decryption);
+}
+
+#[test]
+fn tampered_ciphertext() {
+    let enc = encrypt(&RistrettoSk::generate(), "secret");
+    let enc = modify(&enc, |data| *data.last_mut().unwrap() ^= 1);
+    assert_eq!(enc.unlock("secret").unwrap_err(), UnlockError::Decryption);
+}
+
+#[test]
+fn substituted_pk() {
+    let enc = encrypt(&RistrettoSk::generate(), "secret");
+    let other = RistrettoPk::with(&RistrettoSk::generate()).to_byte_array();
+    let enc = modify(&enc, |data| data[..32].copy_from_slice(&other));
+    assert_eq!(enc.unlock("secret").unwrap_err(), UnlockError::Decryption);
+}
+
+#[test]
+fn rekey() {
+    let sk = RistrettoSk::generate();
+    let enc = encrypt(&sk, "secret");
+    assert_eq!(enc.rekey("wrong", "new").unwrap_err(), UnlockError::Decryption);
+    let rekeyed = enc.rekey("secret", "new").unwrap();
+    assert_eq!(rekeyed.pk(), enc.pk());
+    assert_eq!(rekeyed.unlock("secret").unwrap_err(), UnlockError::Decryption);
+    assert_eq!(RistrettoPk::with(&rekeyed.unlock("new").unwrap()), RistrettoPk::with(&sk));
+}
+
+#[test]
+fn excessive_kdf_costs() {
+    let sk = RistrettoSk::generate();
+    let kdf = KdfParams {
+        m_cost: KDF_MAX_M_COST + 1,
+        ..kdf()
+    };
+    assert_eq!(
+        EncryptedSk::<RistrettoPk>::encrypt_with(&sk, "secret", kdf).unwrap_err(),
+        UnlockError::KdfLimits(KDF_MAX_M_COST + 1, 1, 1)
+    );
+
+    // public key is followed by the 16-byte salt and the little-endian memory size
+    let enc = encrypt(&sk, "secret");
+    let enc = modify(&enc, |data| data[48..52].copy_from_slice(&u32::MAX.to_le_bytes()));
+    assert_eq!(enc.unlock("secret").unwrap_err(), UnlockError::KdfLimits(u32::MAX, 1, 1));
+}
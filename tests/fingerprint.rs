@@ -0,0 +1,84 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Short and long key fingerprints.
+
+use std::str::FromStr;
+
+use ssid::{
+    AnyPk, AnySk, LongFingerprint, LongFingerprintParseError, Pk, RistrettoPk, RistrettoSk,
+    Secp256k1Sk, Sk, LONG_FINGERPRINT_VERSION,
+};
+
+#[test]
+fn long_round_trip() {
+    let fp = RistrettoPk::with(&RistrettoSk::generate()).long_fingerprint();
+    assert_eq!(fp.version(), LONG_FINGERPRINT_VERSION);
+    let s = fp.to_string();
+    assert_eq!(s.len(), 66);
+    assert_eq!(LongFingerprint::from_str(&s), Ok(fp));
+}
+
+#[test]
+fn long_errors() {
+    let fp = RistrettoPk::with(&RistrettoSk::generate()).long_fingerprint();
+    let s = fp.to_string();
+    assert_eq!(
+        LongFingerprint::from_str(&format!("02{}", &s[2..])),
+        Err(LongFingerprintParseError::UnknownVersion(2))
+    );
+    assert_eq!(
+        LongFingerprint::from_str(&s[..64]),
+        Err(LongFingerprintParseError::InvalidLength {
+            expected: 33,
+            found: 32
+        })
+    );
+    assert_eq!(
+        LongFingerprint::from_str(&format!("{s}00")),
+        Err(LongFingerprintParseError::InvalidLength {
+            expected: 33,
+            found: 34
+        })
+    );
+    assert!(matches!(
+        LongFingerprint::from_str(&format!("{}zz", &s[..64])),
+        Err(LongFingerprintParseError::Hex(_))
+    ));
+}
+
+#[test]
+fn short_is_prefix() {
+    let pk = RistrettoPk::with(&RistrettoSk::generate());
+    let fp = pk.long_fingerprint();
+    assert_eq!(pk.fingerprint(), fp.to_short());
+    assert_eq!(pk.fingerprint().as_slice(), &fp.hash().as_slice()[..4]);
+    assert!(fp.to_string()[2..].starts_with(&pk.fingerprint().to_string()));
+}
+
+#[test]
+fn any_matches_typed() {
+    let sk = Secp256k1Sk::generate();
+    let pk = ssid::Secp256k1Pk::with(&sk);
+    let any = AnyPk::with(&AnySk::from(sk));
+    assert_eq!(any.long_fingerprint(), pk.long_fingerprint());
+    assert_eq!(any.fingerprint(), pk.fingerprint());
+}
@@ -0,0 +1,67 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hybrid Ristretto + ML-DSA composite keys and signatures.
+
+#![cfg(feature = "mldsa")]
+
+mod common;
+
+use std::str::FromStr;
+
+use common::{check_identity, check_pk_baid58, check_sign_verify};
+use ssid::{HybridPk, HybridSk, MlDsaPk, Pk, SigHash, Sk};
+
+#[test]
+fn pk_baid58() {
+    check_pk_baid58::<HybridPk>();
+    let pk = HybridPk::with(&HybridSk::generate());
+    assert!(HybridPk::from_str(&pk.pq.to_string()).is_err());
+    assert!(MlDsaPk::from_str(&pk.to_string()).is_err());
+}
+
+#[test]
+fn sign_verify() { check_sign_verify::<HybridPk>() }
+
+#[test]
+fn invalid_classic_sig() {
+    let sk = HybridSk::generate();
+    let pk = HybridPk::with(&sk);
+    let message = SigHash::message([0xA5; 32]);
+    let mut sig = sk.sign(message);
+    sig.classic = sk.classic.sign(SigHash::message([0x5A; 32]));
+    assert!(pk.pq.verify(message, &sig.pq));
+    assert!(!pk.verify(message, &sig));
+}
+
+#[test]
+fn invalid_pq_sig() {
+    let sk = HybridSk::generate();
+    let pk = HybridPk::with(&sk);
+    let message = SigHash::message([0xA5; 32]);
+    let mut sig = sk.sign(message);
+    sig.pq = sk.pq.sign(SigHash::message([0x5A; 32]));
+    assert!(pk.classic.verify(message, &sig.classic));
+    assert!(!pk.verify(message, &sig));
+}
+
+#[test]
+fn identity() { check_identity::<HybridPk>() }
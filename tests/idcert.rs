@@ -0,0 +1,132 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of the identity certificate chains.
+
+mod common;
+
+use common::{closable_seal, closing_proof, seal};
+use ssid::{
+    BindleContent, IdCert, IdCertError, Identity, Proof, Revocation, RistrettoPk, Seal, Ssi,
+};
+use strict_encoding::StrictDumb;
+
+fn push(cert: &mut IdCert, new_identity: Identity, revocation_proof: Proof) {
+    let revocation = Revocation {
+        new_identity,
+        revocation_proof,
+    };
+    cert.revocations.push(revocation).unwrap();
+}
+
+/// Revokes the current identity of the certificate in favour of a new one.
+fn revoke(cert: &mut IdCert, new: Identity) {
+    let proof = closing_proof(cert.identity(), new);
+    push(cert, new, proof);
+}
+
+fn new_identity(seal: Seal) -> Identity { Ssi::<RistrettoPk>::new(seal).cert.identity() }
+
+#[test]
+fn genesis() {
+    let ssi = Ssi::<RistrettoPk>::new(seal());
+    assert_eq!(ssi.cert.verify(), Ok(()));
+    assert_eq!(ssi.cert.bindle().verify(), Ok(()));
+}
+
+#[test]
+fn genesis_sig() {
+    let mut cert = Ssi::<RistrettoPk>::new(seal()).cert;
+    cert.genesis_sig = Ssi::<RistrettoPk>::new(seal()).cert.genesis_sig;
+    assert_eq!(cert.verify(), Err(IdCertError::GenesisSig));
+    assert_eq!(cert.verify_revocations(), Ok(()));
+}
+
+#[test]
+fn revocation() {
+    let mut cert = Ssi::<RistrettoPk>::new(closable_seal(0)).cert;
+    let (new, newer) = (new_identity(closable_seal(1)), new_identity(closable_seal(2)));
+    revoke(&mut cert, new);
+    assert_eq!(cert.verify(), Ok(()));
+    assert_eq!(cert.identity(), new);
+    revoke(&mut cert, newer);
+    assert_eq!(cert.verify(), Ok(()));
+    assert_eq!(cert.identity(), newer);
+}
+
+#[test]
+fn stranger_revocation() {
+    let mut cert = Ssi::<RistrettoPk>::new(closable_seal(0)).cert;
+    let (new, stranger) = (new_identity(closable_seal(1)), new_identity(closable_seal(2)));
+
+    // a stranger reuses the spending of the seal committing to another identity
+    let proof = closing_proof(cert.identity(), new);
+    let mut forged = cert.clone();
+    push(&mut forged, stranger, proof);
+    assert_eq!(forged.verify(), Err(IdCertError::UncommittedRevocation(0)));
+
+    // the spending commits to the revocation of another identity
+    let other = Ssi::<RistrettoPk>::new(closable_seal(0)).cert.identity();
+    let proof = closing_proof(other, stranger);
+    push(&mut cert, stranger, proof);
+    assert_eq!(cert.verify(), Err(IdCertError::UncommittedRevocation(0)));
+}
+
+#[test]
+fn seal_tx_mismatch() {
+    let mut cert = Ssi::<RistrettoPk>::new(seal()).cert;
+    revoke(&mut cert, new_identity(closable_seal(1)));
+    assert_eq!(cert.verify(), Err(IdCertError::SealTxMismatch(0)));
+}
+
+#[test]
+fn seal_not_closed() {
+    let mut cert = Ssi::<RistrettoPk>::new(closable_seal(0)).cert;
+    push(&mut cert, new_identity(closable_seal(1)), Proof::strict_dumb());
+    assert_eq!(cert.verify(), Err(IdCertError::SealNotClosed(0)));
+}
+
+#[test]
+fn proof_mismatch() {
+    let (Seal::Bitcoin(outpoint) | Seal::Liquid(outpoint)) = closable_seal(0);
+    let mut cert = Ssi::<RistrettoPk>::new(Seal::Liquid(outpoint)).cert;
+    push(&mut cert, new_identity(closable_seal(1)), Proof::strict_dumb());
+    assert_eq!(cert.verify(), Err(IdCertError::ProofMismatch(0)));
+}
+
+#[test]
+fn reused() {
+    let ssi = Ssi::<RistrettoPk>::new(closable_seal(0));
+    let new = new_identity(closable_seal(1));
+
+    let mut cert = ssi.cert.clone();
+    let reused = Identity {
+        key: cert.identity().key,
+        seal: new.seal,
+    };
+    revoke(&mut cert, reused);
+    assert_eq!(cert.verify(), Err(IdCertError::KeyReused(0)));
+
+    let mut cert = ssi.cert.clone();
+    revoke(&mut cert, new);
+    revoke(&mut cert, new_identity(closable_seal(0)));
+    assert_eq!(cert.verify(), Err(IdCertError::SealReused(1)));
+}
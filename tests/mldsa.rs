@@ -0,0 +1,68 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ML-DSA-65 keys, signatures and identities, which are much larger than the
+//! classical ones.
+
+#![cfg(feature = "mldsa")]
+
+mod common;
+
+use std::env;
+use std::str::FromStr;
+
+use amplify::confinement::U24;
+use common::{check_cert_encoding, check_identity, check_pk_baid58, check_sign_verify, seal};
+use ssid::{Bindle, IdCert, MlDsaPk, Pk, RistrettoPk, RistrettoSk, SigCert, Sk, Ssi};
+
+#[test]
+fn pk_baid58() {
+    check_pk_baid58::<MlDsaPk>();
+    let other = RistrettoPk::with(&RistrettoSk::generate());
+    assert!(MlDsaPk::from_str(&other.to_string()).is_err());
+}
+
+#[test]
+fn sign_verify() { check_sign_verify::<MlDsaPk>() }
+
+#[test]
+fn identity() { check_identity::<MlDsaPk>() }
+
+#[test]
+fn cert_encoding() { check_cert_encoding::<MlDsaPk, U24>() }
+
+#[test]
+fn signed_bindle() {
+    let ssi = Ssi::<MlDsaPk>::new(seal());
+    let signer = Ssi::<MlDsaPk>::new(seal());
+    let mut bindle = Bindle::<IdCert<MlDsaPk>, MlDsaPk>::new(ssi.cert.clone());
+    let sig = SigCert::new([0xA5; 32], signer.sk(), signer.cert.clone());
+    assert!(sig.verify());
+    bindle.add_sig(sig.clone()).unwrap();
+
+    let path = env::temp_dir().join(format!("ssid-mldsa-{}.ssi", ssi.fingerprint()));
+    bindle.save(&path).unwrap();
+    let restored = Bindle::<IdCert<MlDsaPk>, MlDsaPk>::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restored.sigs(), &[sig]);
+    assert_eq!(restored.verify(), Ok(()));
+    assert_eq!(restored.unbindle(), ssi.cert);
+}
@@ -0,0 +1,47 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Restoring identities from BIP39 mnemonics.
+
+#![cfg(feature = "mnemonic")]
+
+mod common;
+
+use std::str::FromStr;
+
+use common::seal;
+use ssid::{DerivationPath, Pk, RistrettoPk, Ssi};
+
+#[test]
+fn mnemonic_restore() {
+    let path = DerivationPath::from_str("m/44'/0'").unwrap();
+    let (ssi, mnemonic) = Ssi::with_mnemonic(seal(), "secret", &path);
+    let restored = Ssi::from_mnemonic(&mnemonic, "secret", &path, seal());
+    assert_eq!(RistrettoPk::with(restored.sk()), RistrettoPk::with(ssi.sk()));
+    assert_eq!(restored.sk().derivation(), Some(&path));
+    assert_eq!(restored.cert.genesis_id, ssi.cert.genesis_id);
+    assert!(restored.cert.verify().is_ok());
+
+    let other = Ssi::from_mnemonic(&mnemonic, "other", &path, seal());
+    assert_ne!(RistrettoPk::with(other.sk()), RistrettoPk::with(ssi.sk()));
+    let master = Ssi::from_mnemonic(&mnemonic, "secret", &DerivationPath::master(), seal());
+    assert_ne!(RistrettoPk::with(master.sk()), RistrettoPk::with(ssi.sk()));
+}
@@ -0,0 +1,89 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing with keys held by SoftHSM.
+//!
+//! The tests are ignored by default since they require a SoftHSM token:
+//!
+//! ```sh
+//! softhsm2-util --init-token --free --label ssid --pin 1234 --so-pin 1234
+//! SSID_PKCS11_PIN=1234 cargo test --features pkcs11 --test pkcs11 -- --ignored
+//! ```
+//!
+//! The module path defaults to `/usr/lib/softhsm/libsofthsm2.so` and can be
+//! overridden with `SSID_PKCS11_MODULE`.
+
+#![cfg(feature = "pkcs11")]
+
+mod common;
+
+use std::env;
+use std::path::PathBuf;
+
+use common::seal;
+use rand::random;
+use ssid::{HsmError, HsmKeyRef, HsmSigner, IdCert, Pk, SigCert, Signer};
+
+fn module() -> PathBuf {
+    env::var("SSID_PKCS11_MODULE")
+        .unwrap_or_else(|_| "/usr/lib/softhsm/libsofthsm2.so".to_owned())
+        .into()
+}
+
+fn pin() -> String { env::var("SSID_PKCS11_PIN").unwrap_or_else(|_| "1234".to_owned()) }
+
+#[test]
+#[ignore]
+fn genesis_and_sign() {
+    let (module, pin, label) = (module(), pin(), format!("ssid-test-{:08x}", random::<u32>()));
+    let key_ref = HsmKeyRef {
+        module: &module,
+        slot: None,
+        pin: &pin,
+        label: &label,
+    };
+    let signer = HsmSigner::generate(&key_ref).unwrap();
+    let cert = IdCert::with_signer(seal(), &signer).unwrap();
+    let identity = cert.identity();
+    assert_eq!(identity.key, signer.pk());
+    assert!(identity.key.verify(identity, &cert.genesis_sig));
+
+    drop(signer);
+    let signer = HsmSigner::open(&key_ref).unwrap();
+    assert_eq!(signer.pk(), identity.key);
+    let sig = SigCert::with_signer([0x5A; 32], &signer, cert).unwrap();
+    assert!(sig.verify());
+
+    assert!(matches!(HsmSigner::generate(&key_ref), Err(HsmError::KeyExists(_))));
+}
+
+#[test]
+#[ignore]
+fn missing_key() {
+    let (module, pin) = (module(), pin());
+    let key_ref = HsmKeyRef {
+        module: &module,
+        slot: None,
+        pin: &pin,
+        label: "ssid-test-missing",
+    };
+    assert!(matches!(HsmSigner::open(&key_ref), Err(HsmError::KeyNotFound(_))));
+}
@@ -0,0 +1,154 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Linkable ring signatures over Ed25519 identity keys.
+
+#![cfg(feature = "ring")]
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::Confined;
+use amplify::Bytes32;
+use common::seal;
+use ssid::{
+    Bindle, BindleContent, IdCertError, Identity, RingError, RingSig, RingSigCert, RistrettoPk, Ssi,
+};
+
+fn members(n: usize) -> Vec<Ssi<RistrettoPk>> { (0..n).map(|_| Ssi::new(seal())).collect() }
+
+fn ring(members: &[Ssi<RistrettoPk>]) -> Vec<Identity<RistrettoPk>> {
+    members.iter().map(|ssi| ssi.cert.identity()).collect()
+}
+
+#[test]
+fn sign_verify() {
+    let members = members(4);
+    let ring = ring(&members);
+    for ssi in &members {
+        let sig = RingSig::sign(b"vote: yes", b"poll 1", &ring, ssi.sk()).unwrap();
+        assert_eq!(sig.verify(b"vote: yes", b"poll 1", &ring), Ok(()));
+    }
+}
+
+#[test]
+fn non_member() {
+    let members = members(3);
+    let stranger = Ssi::<RistrettoPk>::new(seal());
+    assert_eq!(
+        RingSig::sign(b"vote: yes", b"poll 1", &ring(&members), stranger.sk()),
+        Err(RingError::SignerNotInRing)
+    );
+
+    // a signature made for another ring doesn't verify against this one
+    let mut other = ring(&members[1..]);
+    other.push(stranger.cert.identity());
+    let sig = RingSig::sign(b"vote: yes", b"poll 1", &other, stranger.sk()).unwrap();
+    assert_eq!(
+        sig.verify(b"vote: yes", b"poll 1", &ring(&members)),
+        Err(RingError::InvalidSignature)
+    );
+}
+
+#[test]
+fn wrong_message_or_scope() {
+    let members = members(3);
+    let ring = ring(&members);
+    let sig = RingSig::sign(b"vote: yes", b"poll 1", &ring, members[1].sk()).unwrap();
+    assert_eq!(sig.verify(b"vote: no", b"poll 1", &ring), Err(RingError::InvalidSignature));
+    assert_eq!(sig.verify(b"vote: yes", b"poll 2", &ring), Err(RingError::InvalidSignature));
+}
+
+#[test]
+fn linking() {
+    let members = members(3);
+    let ring = ring(&members);
+    let sig1 = RingSig::sign(b"vote: yes", b"poll 1", &ring, members[0].sk()).unwrap();
+    let sig2 = RingSig::sign(b"vote: no", b"poll 1", &ring, members[0].sk()).unwrap();
+    let sig3 = RingSig::sign(b"vote: yes", b"poll 1", &ring, members[1].sk()).unwrap();
+    assert_eq!(sig1.key_image, sig2.key_image);
+    assert_ne!(sig1.key_image, sig3.key_image);
+}
+
+#[test]
+fn cross_scope_unlinkability() {
+    let members = members(3);
+    let ring = ring(&members);
+    let sig1 = RingSig::sign(b"vote: yes", b"poll 1", &ring, members[0].sk()).unwrap();
+    let sig2 = RingSig::sign(b"vote: yes", b"poll 2", &ring, members[0].sk()).unwrap();
+    assert_ne!(sig1.key_image, sig2.key_image);
+}
+
+#[test]
+fn tampered_response() {
+    let members = members(3);
+    let ring = ring(&members);
+    let sig = RingSig::sign(b"vote: yes", b"poll 1", &ring, members[2].sk()).unwrap();
+
+    let mut responses = sig.responses.to_vec();
+    let mut data = responses[1].to_byte_array();
+    data[0] ^= 1;
+    responses[1] = Bytes32::from(data);
+    let mut tampered = sig.clone();
+    tampered.responses = Confined::try_from(responses).unwrap();
+    assert_eq!(tampered.verify(b"vote: yes", b"poll 1", &ring), Err(RingError::InvalidSignature));
+
+    let mut truncated = sig.clone();
+    truncated.responses = Confined::try_from(sig.responses[..2].to_vec()).unwrap();
+    assert_eq!(
+        truncated.verify(b"vote: yes", b"poll 1", &ring),
+        Err(RingError::RingSizeMismatch {
+            expected: 3,
+            found: 2
+        })
+    );
+}
+
+#[test]
+fn cert_bindle() {
+    let members = members(3);
+    let certs = Confined::try_from_iter(members.iter().map(|ssi| ssi.cert.clone())).unwrap();
+    let scope = Confined::try_from(b"poll 1".to_vec()).unwrap();
+    let cert = RingSigCert::new([0xA5; 32], scope, certs, members[1].sk()).unwrap();
+    assert_eq!(cert.verify(), Ok(()));
+
+    let bindle = cert.clone().bindle();
+    let restored = Bindle::<RingSigCert>::from_str(&bindle.to_string()).unwrap();
+    assert_eq!(restored.id(), cert.key_image());
+    assert_eq!(restored.unbindle(), cert);
+
+    let mut forged = cert.clone();
+    forged.digest = Bytes32::from([0x5A; 32]);
+    assert_eq!(forged.verify(), Err(RingError::InvalidSignature));
+}
+
+#[test]
+fn cert_invalid_member() {
+    let members = members(3);
+    let mut certs = members.iter().map(|ssi| ssi.cert.clone()).collect::<Vec<_>>();
+    certs[2].genesis_sig = Ssi::<RistrettoPk>::new(seal()).cert.genesis_sig;
+    let scope = Confined::try_from(b"poll 1".to_vec()).unwrap();
+    let cert =
+        RingSigCert::new([0xA5; 32], scope, Confined::try_from(certs).unwrap(), members[0].sk())
+            .unwrap();
+    assert_eq!(cert.verify(), Err(RingError::InvalidCert(2, IdCertError::GenesisSig)));
+}
@@ -0,0 +1,102 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ssid::{Bindle, Pk, RistrettoPk, RistrettoSk, ShareError, Sk, SkShare};
+
+fn split(sk: &RistrettoSk, threshold: u8, total: u8) -> Vec<SkShare> {
+    SkShare::split(sk, threshold, total).unwrap()
+}
+
+#[test]
+fn threshold_subsets() {
+    let sk = RistrettoSk::generate();
+    let pk = RistrettoPk::with(&sk);
+    let shares = split(&sk, 3, 5);
+    assert_eq!(shares.len(), 5);
+    for mask in 0u8..32 {
+        let subset = shares
+            .iter()
+            .enumerate()
+            .filter(|(no, _)| mask & (1 << no) != 0)
+            .map(|(_, share)| share.clone())
+            .collect::<Vec<_>>();
+        match SkShare::combine(&subset) {
+            Ok(recovered) => {
+                assert!(subset.len() >= 3);
+                assert_eq!(RistrettoPk::with(&recovered), pk);
+            }
+            Err(err) => {
+                assert!(subset.len() < 3);
+                assert_eq!(err, ShareError::NotEnoughShares {
+                    present: subset.len(),
+                    threshold: if subset.is_empty() { 2 } else { 3 },
+                });
+            }
+        }
+    }
+}
+
+#[test]
+fn invalid_threshold() {
+    let sk = RistrettoSk::generate();
+    assert_eq!(
+        SkShare::<RistrettoPk>::split(&sk, 1, 3).unwrap_err(),
+        ShareError::InvalidThreshold {
+            threshold: 1,
+            total: 3
+        }
+    );
+    assert_eq!(
+        SkShare::<RistrettoPk>::split(&sk, 4, 3).unwrap_err(),
+        ShareError::InvalidThreshold {
+            threshold: 4,
+            total: 3
+        }
+    );
+}
+
+#[test]
+fn duplicate_share() {
+    let shares = split(&RistrettoSk::generate(), 3, 5);
+    let subset = [shares[0].clone(), shares[1].clone(), shares[0].clone()];
+    assert_eq!(
+        SkShare::combine(&subset).unwrap_err(),
+        ShareError::DuplicateShare(shares[0].index())
+    );
+}
+
+#[test]
+fn mismatched_keys() {
+    let shares = split(&RistrettoSk::generate(), 2, 3);
+    let other = split(&RistrettoSk::generate(), 2, 3);
+    let subset = [shares[0].clone(), other[1].clone()];
+    assert_eq!(SkShare::combine(&subset).unwrap_err(), ShareError::MismatchedShares);
+}
+
+#[test]
+fn bindle_round_trip() {
+    let sk = RistrettoSk::generate();
+    let shares = split(&sk, 2, 3)
+        .into_iter()
+        .map(|share| share.bindle().to_string().parse::<Bindle<SkShare>>().unwrap().unbindle())
+        .collect::<Vec<_>>();
+    assert_eq!(RistrettoPk::with(&SkShare::combine(&shares[1..]).unwrap()), RistrettoPk::with(&sk));
+}
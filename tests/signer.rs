@@ -0,0 +1,83 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Identity operations performed through the [`Signer`] abstraction.
+
+mod common;
+
+use common::seal;
+use ssid::{
+    IdCert, MemSigner, Pk, RecordingSigner, RistrettoPk, RistrettoSk, SigCert, SigHash, Signer, Sk,
+    Ssi,
+};
+
+const DIGEST: [u8; 32] = [0xA5; 32];
+
+#[test]
+fn genesis() {
+    let seal = seal();
+    let signer = RecordingSigner::new(MemSigner::from(RistrettoSk::generate()));
+    let cert = IdCert::with_signer(seal, &signer).unwrap();
+
+    let identity = cert.identity();
+    assert_eq!(identity.key, signer.pk());
+    assert_eq!(identity.seal, seal);
+    assert_eq!(signer.requests(), vec![SigHash::from(identity)]);
+    assert!(identity.key.verify(identity, &cert.genesis_sig));
+}
+
+#[test]
+fn sig_cert() {
+    let ssi = Ssi::<RistrettoPk>::new(seal());
+    let signer = RecordingSigner::new(&ssi);
+    let cert = SigCert::with_signer(DIGEST, &signer, ssi.cert.clone()).unwrap();
+
+    assert_eq!(signer.requests(), vec![SigHash::message(DIGEST)]);
+    assert!(cert.verify());
+    assert_eq!(cert.sig.digest.to_byte_array(), DIGEST);
+}
+
+#[test]
+fn external_ssi() {
+    let signer = RecordingSigner::new(MemSigner::from(RistrettoSk::generate()));
+    let ssi = Ssi::with_signer(signer, seal()).unwrap();
+    assert_eq!(ssi.cert.verify(), Ok(()));
+    assert_eq!(ssi.pk(), ssi.cert.identity().key);
+
+    let cert = SigCert::with_signer(DIGEST, &ssi, ssi.cert.clone()).unwrap();
+    assert!(cert.verify());
+    assert_eq!(ssi.signer.requests(), vec![
+        SigHash::from(ssi.cert.identity()),
+        SigHash::message(DIGEST)
+    ]);
+}
+
+#[test]
+fn constructors_delegate() {
+    let seal = seal();
+    let ssi = Ssi::<RistrettoPk>::with(RistrettoSk::from_seed([7; 32]), seal);
+    let signer = MemSigner::from(RistrettoSk::from_seed([7; 32]));
+    assert_eq!(IdCert::with_signer(seal, &signer).unwrap(), ssi.cert);
+    assert_eq!(
+        SigCert::new(DIGEST, ssi.sk(), ssi.cert.clone()),
+        SigCert::with_signer(DIGEST, &signer, ssi.cert.clone()).unwrap()
+    );
+}
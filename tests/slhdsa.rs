@@ -0,0 +1,79 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SLH-DSA-SHA2-128s keys, signatures and identities.
+
+#![cfg(feature = "slhdsa")]
+
+mod common;
+
+use std::str::FromStr;
+
+use amplify::confinement::U24;
+use common::{check_cert_encoding, check_identity, check_pk_baid58, check_sign_verify};
+use ssid::{
+    Digest, Pk, RistrettoPk, RistrettoSk, SigHash, Signature, Sk, SlhDsaPk, SlhDsaSig, SlhDsaSk,
+};
+use strict_encoding::{StrictDeserialize, StrictSerialize};
+
+#[test]
+fn pk_baid58() {
+    check_pk_baid58::<SlhDsaPk>();
+    let pk = SlhDsaPk::with(&SlhDsaSk::generate());
+    // Ristretto keys have the same payload length
+    let other = RistrettoPk::with(&RistrettoSk::generate());
+    assert!(SlhDsaPk::from_str(&other.to_string()).is_err());
+    assert!(RistrettoPk::from_str(&pk.to_string()).is_err());
+}
+
+#[test]
+fn sign_verify() { check_sign_verify::<SlhDsaPk>() }
+
+#[test]
+fn identity() { check_identity::<SlhDsaPk>() }
+
+#[test]
+fn cert_encoding() { check_cert_encoding::<SlhDsaPk, U24>() }
+
+#[test]
+fn sk_roundtrip() {
+    let sk = SlhDsaSk::generate();
+    let data = sk.to_strict_serialized::<U24>().unwrap();
+    assert_eq!(data.len(), 64);
+    let restored = SlhDsaSk::from_strict_serialized::<U24>(data).unwrap();
+    assert_eq!(SlhDsaPk::with(&restored), SlhDsaPk::with(&sk));
+}
+
+#[test]
+fn signature_roundtrip() {
+    let sk = SlhDsaSk::generate();
+    let pk = SlhDsaPk::with(&sk);
+    let digest = Digest::from([0xA5u8; 32]);
+    let sig = Signature {
+        digest,
+        sig: sk.sign(SigHash::message(digest)),
+    };
+    let data = sig.to_strict_serialized::<U24>().unwrap();
+    assert_eq!(data.len(), 32 + 7856);
+    let restored = Signature::<SlhDsaSig>::from_strict_serialized::<U24>(data).unwrap();
+    assert_eq!(restored, sig);
+    assert!(pk.verify(restored.sig_hash(), &restored.sig));
+}
@@ -0,0 +1,103 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SLIP-10 ed25519 test vector 1 for the hierarchical derivation of
+//! [`RistrettoSk`].
+
+use std::str::FromStr;
+
+use amplify::confinement::U24;
+use amplify::hex::FromHex;
+use ssid::{BindleContent, DerivationPath, Pk, RistrettoPk, RistrettoSk};
+use strict_encoding::StrictSerialize;
+
+const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+const VECTORS: [(&str, &str); 4] = [
+    ("m", "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed"),
+    ("m/0'", "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"),
+    ("m/0'/1'", "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187"),
+    ("m/0'/1'/2'", "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1"),
+];
+
+#[test]
+fn derivation() {
+    let seed = Vec::<u8>::from_hex(SEED).unwrap();
+    for (path, pk) in VECTORS {
+        let path = DerivationPath::from_str(path).unwrap();
+        let sk = RistrettoSk::derive(&seed, &path);
+        assert_eq!(RistrettoPk::with(&sk).to_byte_array(), <[u8; 32]>::from_hex(pk).unwrap());
+        assert_eq!(sk.derivation(), Some(&path));
+    }
+    assert_eq!(
+        RistrettoPk::with(&RistrettoSk::from_master_seed(&seed)).to_byte_array(),
+        <[u8; 32]>::from_hex(VECTORS[0].1).unwrap()
+    );
+}
+
+#[test]
+fn path_display() {
+    let path = DerivationPath::from_str("m/44h/0'/7'").unwrap();
+    assert_eq!(path.indexes(), &[44, 0, 7]);
+    assert_eq!(path.to_string(), "m/44'/0'/7'");
+    assert!(DerivationPath::from_str("m/1").is_err());
+    assert!(DerivationPath::from_str("0'/1'").is_err());
+    assert!(DerivationPath::from_str("m/2147483648'").is_err());
+}
+
+#[test]
+fn bindle_header() {
+    let seed = Vec::<u8>::from_hex(SEED).unwrap();
+    let path = DerivationPath::from_str("m/0'/1'").unwrap();
+    let sk = RistrettoSk::derive(&seed, &path);
+    assert_eq!(sk.bindle_headers().get("Derivation"), Some(&"m/0'/1'".to_string()));
+    let restored = sk.bindle().to_string().parse::<ssid::Bindle<RistrettoSk>>().unwrap();
+    assert_eq!(restored.derivation(), Some(&path));
+}
+
+#[test]
+fn legacy_bindle() {
+    let seed = [7u8; 32];
+    let sk = RistrettoSk::from_seed(seed);
+    let pk = RistrettoPk::with(&sk);
+    let data = [seed, pk.to_byte_array()].concat();
+    let derived = RistrettoSk::derive(&seed, &DerivationPath::from_str("m/0'").unwrap());
+    assert_eq!(derived.to_strict_serialized::<U24>().unwrap().len(), data.len());
+
+    let text = format!(
+        "-----BEGIN SSID SECRET KEY-----\n\n{}\n\n-----END SSID SECRET KEY-----",
+        base85::encode(&data)
+    );
+    let restored = text.parse::<ssid::Bindle<RistrettoSk>>().unwrap();
+    assert_eq!(restored.bindle_id(), pk);
+    assert_eq!(restored.derivation(), None);
+    assert_eq!(restored.unbindle().to_strict_serialized::<U24>().unwrap().release(), data);
+}
+
+#[test]
+fn invalid_derivation_header() {
+    let sk = RistrettoSk::from_seed([7u8; 32]);
+    let text = sk.bindle().to_string().replacen("\n\n", "\nDerivation: m/1\n\n", 1);
+    assert!(matches!(
+        text.parse::<ssid::Bindle<RistrettoSk>>(),
+        Err(ssid::BindleParseError::InvalidHeader(_))
+    ));
+}
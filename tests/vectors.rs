@@ -0,0 +1,110 @@
+// Self-sovereign identity (SSID)
+//
+// SPDX-License-Identifier: Apache-2.0
+//
+// Written in 2023-204 by
+//     Cypher<cypher@cyphernet.io>
+//
+// Copyright 2023-2024 Cyphernet DAO, Switzerland
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Known-answer tests for deterministic signing with [`RistrettoSk`].
+//!
+//! Key vectors are taken from RFC 8032 section 7.1; signatures are produced in
+//! deterministic RFC 8032 mode over the tagged identity hashes. Conformance of
+//! the deterministic signing to the RFC 8032 signatures is checked by the unit
+//! tests of the key type.
+
+mod common;
+
+use amplify::hex::FromHex;
+use common::seal;
+use ssid::{Digest, IdCert, Identity, Pk, RistrettoPk, RistrettoSk, SigHash};
+
+struct Vector {
+    seed: &'static str,
+    pk: &'static str,
+    sig_hash: &'static str,
+    sig: &'static str,
+}
+
+const VECTORS: [Vector; 3] = [
+    Vector {
+        seed: "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        pk: "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        sig_hash: "491627f8622c061c1506b69034d5d2de9ad7cda33e1c0b7fc45e0879c52be014",
+        sig: "1fd1657be9693bc598d6f7b70060a716fcc589cd8b4449153683813e4322a61d\
+              d8c45e93cae88f58d91b736477f2e88b1ef65ff0771c75183675c139256ccc0f",
+    },
+    Vector {
+        seed: "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        pk: "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        sig_hash: "d238803bc75d8cacffde80b301658159039440cfc6ded16c466f721e1d2ceb37",
+        sig: "ff77c9714a1c31f41ebb3da05723abd5e7b4a2165c5578920e548a780103fbcd\
+              dfc13abf9393fcaec33ea7d748e23fc8a4d492e1425fe715391e2d4e7f739306",
+    },
+    Vector {
+        seed: "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        pk: "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        sig_hash: "1ef3c2c8b98843e828368ae8ff0f1ede30f0ada3a7f81a4828b61ef12ef12c51",
+        sig: "a4ad3c6f8389577741976d5aa358221af4fa79b18093d10f7693c0695d4e0541\
+              ccd8c9fba80e0109895c10b8c7a4c06d74cb1f304a606e5d5e7826ff48ca440b",
+    },
+];
+
+fn sk(vector: &Vector) -> RistrettoSk {
+    RistrettoSk::from_seed(<[u8; 32]>::from_hex(vector.seed).unwrap())
+}
+
+fn identity(vector: &Vector) -> Identity {
+    Identity {
+        key: RistrettoPk::with(&sk(vector)),
+        seal: seal(),
+    }
+}
+
+#[test]
+fn keys() {
+    for vector in &VECTORS {
+        let pk = RistrettoPk::with(&sk(vector));
+        assert_eq!(pk.to_byte_array(), <[u8; 32]>::from_hex(vector.pk).unwrap());
+    }
+}
+
+#[test]
+fn identity_sig_hashes() {
+    for vector in &VECTORS {
+        assert_eq!(
+            SigHash::from(identity(vector)).to_byte_array(),
+            <[u8; 32]>::from_hex(vector.sig_hash).unwrap()
+        );
+    }
+}
+
+#[test]
+fn genesis_signatures() {
+    for vector in &VECTORS {
+        let identity = identity(vector);
+        let cert = IdCert::new(identity, sk(vector).sign_deterministic(identity));
+        assert_eq!(cert.genesis_sig.to_byte_array(), <[u8; 64]>::from_hex(vector.sig).unwrap());
+        assert!(identity.key.verify(identity, &cert.genesis_sig));
+    }
+}
+
+#[test]
+fn deterministic() {
+    let sk = sk(&VECTORS[0]);
+    let msg = SigHash::message(Digest::from([0xA5u8; 32]));
+    assert_eq!(sk.sign_deterministic(msg), sk.sign_deterministic(msg));
+}